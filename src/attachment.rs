@@ -0,0 +1,70 @@
+// This file contains the download of the JSON files attached to the issues, used as their entry data.
+
+use crate::config::Config;
+use crate::extract;
+use crate::github_api_responses::Issue;
+use crate::logging::log_info;
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// The maximum size of a downloaded attachment, in bytes.
+const MAX_ATTACHMENT_SIZE: usize = 1024 * 1024;
+
+/// This function downloads the JSON file attached to every issue whose body has no data comments,
+/// the inline code block taking precedence over the attachment.
+/// Only the attachment links to `github.com` are followed (see `extract::find_json_attachment`).
+///
+/// ## Arguments
+/// - `config`: A reference to the `Config` struct that contains the network options.
+/// - `issues`: The issues whose attachments are downloaded.
+///
+/// ## Returns
+/// The content of the attachment of every issue having one, by the ID of the issue,
+/// or the reason why it could not be downloaded (e.g. the HTTP client could not be built).
+pub(crate) async fn download_attachments(
+    config: &Config,
+    issues: &[Issue],
+) -> HashMap<usize, Result<String, String>> {
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(config.network.timeout_seconds))
+        .user_agent(config.network.user_agent())
+        .build()
+        .map_err(|e| format!("Failed to Build HTTP Client: {}", e));
+
+    let mut attachments: HashMap<usize, Result<String, String>> = HashMap::new();
+    for issue in issues {
+        let Some(body) = issue.body.as_deref() else {
+            continue;
+        };
+        if extract::has_data_markers(body) {
+            continue;
+        }
+        let Some(url) = extract::find_json_attachment(body) else {
+            continue;
+        };
+        log_info!("Downloading attachment {}, ID: {}", url, issue.id);
+        let attachment = match &client {
+            Ok(client) => download(client, url).await,
+            Err(reason) => Err(reason.clone()),
+        };
+        attachments.insert(issue.id, attachment);
+    }
+    attachments
+}
+
+/// Downloads an attachment, returning its content, or the reason why it could not be downloaded.
+async fn download(client: &reqwest::Client, url: &str) -> Result<String, String> {
+    let res = client.get(url).send().await.map_err(|e| e.to_string())?;
+    if !res.status().is_success() {
+        return Err(res.status().to_string());
+    }
+    let bytes = res.bytes().await.map_err(|e| e.to_string())?;
+    if bytes.len() > MAX_ATTACHMENT_SIZE {
+        return Err(format!(
+            "the file is too large ({} bytes, at most {} allowed)",
+            bytes.len(),
+            MAX_ATTACHMENT_SIZE
+        ));
+    }
+    String::from_utf8(bytes.to_vec()).map_err(|_| "the file is not UTF-8 text".to_string())
+}
@@ -0,0 +1,236 @@
+// This file contains the report of the entries changed since the previous run.
+
+use crate::config::{OutputConfig, OutputKeys};
+use crate::generator;
+use crate::key_case;
+use crate::logging::log_warn;
+use indexmap::IndexMap;
+use serde::Serialize;
+use serde_json::Value;
+
+/// The structure of the report of the entries changed since the previous run, written to `changes.json`.
+#[derive(Serialize, Default)]
+pub(crate) struct Changes {
+    /// The entries that were not in the previous output.
+    pub(crate) added: Vec<Value>,
+    /// The entries of the previous output that are not in the new one.
+    pub(crate) removed: Vec<Value>,
+    /// The entries whose data changed, with their previous and new data.
+    pub(crate) updated: Vec<UpdatedEntry>,
+}
+
+/// The structure of an entry whose data changed since the previous run.
+#[derive(Serialize)]
+pub(crate) struct UpdatedEntry {
+    /// The data of the entry in the previous output.
+    pub(crate) before: Value,
+    /// The data of the entry in the new output.
+    pub(crate) after: Value,
+}
+
+/// This function compares the entries of the previous output with the ones of the new output.
+///
+/// The entries are identified by the ID of their issue, or by the value of their identity field if it is set,
+/// compared like the duplicate detection (see `generator::identity_value`), and the entries without an identity
+/// (e.g. the static entries, when they are identified by their issue ID) are ignored. An entry that is
+/// in several groups is only reported once, and when several entries of a same output have the same identity
+/// (e.g. with `on_duplicate = "keep_all"`), only the first one, in the order of the groups, is compared.
+///
+/// ## Arguments
+/// - `output`: A reference to the `OutputConfig` struct that contains the key names.
+/// - `identity_field`: The field identifying the entries, before its conversion to `output.key_case`,
+///   or `None` to identify them by their issue ID.
+/// - `previous`: The data of the previous output file, as parsed from the file.
+/// - `previous_ids`: The issue IDs of the entries of the previous output file, in the order of the file
+///   (see `GroupOutput::collect_entry_ids`), only used without an identity field.
+/// - `current`: The JSON data of the groups of the new output, with the keys converted to `output.key_case`.
+/// - `current_ids`: The issue IDs of the entries of the new output, in the same order.
+///
+/// ## Returns
+/// The added, removed and updated entries.
+pub(crate) fn diff_entries(
+    output: &OutputConfig,
+    identity_field: Option<&str>,
+    previous: &Value,
+    previous_ids: &[Option<usize>],
+    current: &[Value],
+    current_ids: &[Option<usize>],
+) -> Changes {
+    // The key names of the files are converted to the key case, if any.
+    let convert = |key: &str| match output.key_case {
+        Some(case) => key_case::convert_key(key, case),
+        None => key.to_string(),
+    };
+    let keys = match output.key_case {
+        Some(case) => key_case::convert_output_keys(&output.keys, case),
+        None => output.keys.clone(),
+    };
+    let identity_field = identity_field.map(convert);
+
+    // The previous output may be wrapped in an envelope object.
+    let previous_groups = match previous {
+        Value::Object(envelope) => envelope.get(&convert(&output.envelope_key)),
+        groups => Some(groups),
+    };
+    let mut previous_list: Vec<&Value> = Vec::new();
+    if let Some(Value::Array(groups)) = previous_groups {
+        collect_entries(groups, &keys, &mut previous_list);
+    }
+    // The issue IDs of the previous entries are only known if they match the previous output file.
+    if identity_field.is_none() && previous_ids.len() != previous_list.len() {
+        log_warn!(
+            "Warning: The Issue IDs of the Previous Output Are Missing or Outdated, All Entries Reported as Added."
+        );
+        previous_list.clear();
+    }
+    let previous_entries = identify_entries(previous_list, identity_field.as_deref(), previous_ids);
+    let mut current_list: Vec<&Value> = Vec::new();
+    collect_entries(current, &keys, &mut current_list);
+    let current_entries = identify_entries(current_list, identity_field.as_deref(), current_ids);
+
+    let mut changes = Changes::default();
+    for (identity, entry) in &current_entries {
+        match previous_entries.get(identity) {
+            None => changes.added.push((*entry).clone()),
+            Some(before) if before != entry => changes.updated.push(UpdatedEntry {
+                before: (*before).clone(),
+                after: (*entry).clone(),
+            }),
+            Some(_) => {}
+        }
+    }
+    for (identity, entry) in &previous_entries {
+        if !current_entries.contains_key(identity) {
+            changes.removed.push((*entry).clone());
+        }
+    }
+    changes
+}
+
+/// Adds the entries of a list of groups, and recursively of their child groups, to a list,
+/// the entries of a group before the ones of its child groups.
+fn collect_entries<'a>(groups: &'a [Value], keys: &OutputKeys, entries: &mut Vec<&'a Value>) {
+    for group in groups {
+        if let Some(Value::Array(group_entries)) = group.get(&keys.entries) {
+            entries.extend(group_entries);
+        }
+        if let Some(Value::Array(children)) = group.get(&keys.children) {
+            collect_entries(children, keys, entries);
+        }
+    }
+}
+
+/// Maps a list of entries by their identity, the value of their identity field if it is set,
+/// or their issue ID (from `ids`, in the order of the entries) otherwise, keeping the first occurrence of every entry.
+fn identify_entries<'a>(
+    entries: Vec<&'a Value>,
+    identity_field: Option<&str>,
+    ids: &[Option<usize>],
+) -> IndexMap<String, &'a Value> {
+    let mut identified: IndexMap<String, &'a Value> = IndexMap::new();
+    for (index, entry) in entries.into_iter().enumerate() {
+        let identity = match identity_field {
+            Some(field) => generator::identity_value(entry.get(field)),
+            None => ids.get(index).copied().flatten().map(|id| id.to_string()),
+        };
+        if let Some(identity) = identity {
+            identified.entry(identity).or_insert(entry);
+        }
+    }
+    identified
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn group(entries: Value) -> Value {
+        json!({ "group": "group1", "groupName": "Group 1", "groupDesc": "", "entries": entries })
+    }
+
+    #[test]
+    fn identifies_entries_by_issue_id_by_default() {
+        let output = OutputConfig::default();
+        let previous = json!([group(json!([
+            { "name": "A", "url": "https://a.example.com" },
+            { "name": "B", "url": "https://b.example.com" },
+        ]))]);
+        // The URL of the entry of issue 1 changed, it is still the same entry.
+        let current = [group(json!([
+            { "name": "A", "url": "https://a.example.org" },
+            { "name": "C", "url": "https://c.example.com" },
+        ]))];
+        let changes = diff_entries(
+            &output,
+            None,
+            &previous,
+            &[Some(1), Some(2)],
+            &current,
+            &[Some(1), Some(3)],
+        );
+        assert_eq!(
+            changes.added,
+            vec![json!({ "name": "C", "url": "https://c.example.com" })]
+        );
+        assert_eq!(
+            changes.removed,
+            vec![json!({ "name": "B", "url": "https://b.example.com" })]
+        );
+        assert_eq!(changes.updated.len(), 1);
+        assert_eq!(changes.updated[0].after["url"], "https://a.example.org");
+    }
+
+    #[test]
+    fn reports_all_entries_as_added_without_previous_ids() {
+        let output = OutputConfig::default();
+        let previous = json!([group(json!([{ "name": "A" }]))]);
+        let current = [group(json!([{ "name": "A" }]))];
+        let changes = diff_entries(&output, None, &previous, &[], &current, &[Some(1)]);
+        assert_eq!(changes.added.len(), 1);
+        assert!(changes.removed.is_empty());
+    }
+
+    #[test]
+    fn ignores_static_entries_identified_by_issue_id() {
+        let output = OutputConfig::default();
+        let current = [group(json!([{ "name": "Static" }, { "name": "A" }]))];
+        let changes = diff_entries(&output, None, &Value::Null, &[], &current, &[None, Some(1)]);
+        assert_eq!(changes.added, vec![json!({ "name": "A" })]);
+    }
+
+    #[test]
+    fn compares_identity_fields_like_the_duplicate_detection() {
+        let output = OutputConfig::default();
+        let previous = json!({ "groups": [group(json!([
+            { "url": "https://A.example.com/", "name": "A" },
+            { "id": 7, "name": "Seven" },
+        ]))] });
+        let current = [group(json!([
+            { "url": " https://a.example.com", "name": "A" },
+            { "id": 7, "name": "Seven" },
+        ]))];
+        let changes = diff_entries(&output, Some("url"), &previous, &[], &current, &[]);
+        assert!(changes.added.is_empty());
+        assert!(changes.removed.is_empty());
+        assert_eq!(changes.updated.len(), 1);
+
+        // Numeric identities are compared as they are.
+        let changes = diff_entries(&output, Some("id"), &previous, &[], &current, &[]);
+        assert!(changes.added.is_empty());
+        assert!(changes.removed.is_empty());
+        assert!(changes.updated.is_empty());
+    }
+
+    #[test]
+    fn reports_entries_of_child_groups_once() {
+        let output = OutputConfig::default();
+        let mut parent = group(json!([{ "url": "https://a.example.com" }]));
+        parent["children"] = json!([group(json!([
+            { "url": "https://a.example.com" },
+            { "url": "https://b.example.com" },
+        ]))]);
+        let changes = diff_entries(&output, Some("url"), &Value::Null, &[], &[parent], &[]);
+        assert_eq!(changes.added.len(), 2);
+    }
+}
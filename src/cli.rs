@@ -0,0 +1,216 @@
+// This file contains the parsing of the command line arguments of the script.
+
+use crate::logging::LogFormat;
+use std::time::Duration;
+
+/// The usage of the script, printed with `--help` or when the arguments are invalid.
+pub(crate) const USAGE: &str = "\
+Usage: script.ers [OPTIONS]
+
+Options:
+  --watch <interval>  Keep running and regenerate the data every <interval>,
+                      e.g. `30s`, `5m` or `1h` (a bare number is in minutes).
+  --strict            Fail the run if any issue is rejected (same as `generation.strict`).
+  --include-drafts    Include the draft entries (same as `generation.include_drafts`).
+  --since <date>      Only process the issues created (or updated) since <date>, e.g.
+                      `2025-01-01` or `2025-01-01T00:00:00Z` (same as `generation.since`).
+  --timeout <seconds> Cancel the run and exit with an error if it takes longer than <seconds>,
+                      without writing the output files (in watch mode, for every run).
+  --issues-file <file>
+                      Read the issues from a saved JSON file of the GitHub API response instead
+                      of fetching them, e.g. for offline runs (same as `github.issues_file`).
+  --dump-issues <file>
+                      Write all the fetched issues to <file>, to be read back with --issues-file.
+  --profile <name>    Merge the `[profiles.<name>]` table over the configuration
+                      (same as the `BFLDG_PROFILE` environment variable).
+  --skip-label-check  Do not check that the configured labels exist (e.g. for offline runs).
+  --log-format <format>
+                      Write the logs as `text` (the default) or as `json` lines.
+  --check             Check the configuration and the access to the repository with the
+                      credentials, and exit without fetching the issues.
+  --list-labels       Print the labels of the valid entries, by number of entries, and exit
+                      without writing the output files.
+  --init              Write a starter config.toml in the current directory and exit.
+  --force             Overwrite the existing config.toml with --init.
+  --emit-schema <file>
+                      Write the JSON Schema of the entry data to <file> and exit.
+  -h, --help          Print this help message.";
+
+/// The command line arguments of the script.
+#[derive(Default, Debug)]
+pub(crate) struct Cli {
+    /// The interval between two generations in watch mode, if enabled with `--watch <interval>`.
+    pub(crate) watch: Option<Duration>,
+    /// Whether the run fails if any issue is rejected, enabled with `--strict`.
+    pub(crate) strict: bool,
+    /// Whether the draft entries are included, enabled with `--include-drafts`.
+    pub(crate) include_drafts: bool,
+    /// The date the processed issues must be created (or updated) since, if set with `--since <date>`.
+    pub(crate) since: Option<String>,
+    /// The maximum duration of a run, if set with `--timeout <seconds>`.
+    pub(crate) timeout: Option<Duration>,
+    /// The file to read the issues from instead of fetching them, if set with `--issues-file <file>`.
+    pub(crate) issues_file: Option<String>,
+    /// The file the fetched issues are written to, if set with `--dump-issues <file>`.
+    pub(crate) dump_issues: Option<String>,
+    /// The configuration profile merged over the configuration, if selected with `--profile <name>`.
+    pub(crate) profile: Option<String>,
+    /// Whether the check of the configured labels is skipped, with `--skip-label-check`.
+    pub(crate) skip_label_check: bool,
+    /// The format of the logs, set with `--log-format <format>`.
+    pub(crate) log_format: LogFormat,
+    /// Whether only the configuration and the access to the repository are checked, with `--check`.
+    pub(crate) check: bool,
+    /// Whether the labels of the valid entries are requested with `--list-labels`.
+    pub(crate) list_labels: bool,
+    /// Whether a starter configuration file is requested with `--init`.
+    pub(crate) init: bool,
+    /// Whether `--init` can overwrite an existing configuration file, enabled with `--force`.
+    pub(crate) force: bool,
+    /// The file to write the JSON Schema of the entry data to, if requested with `--emit-schema <file>`.
+    pub(crate) emit_schema: Option<String>,
+    /// Whether the help message is requested.
+    pub(crate) help: bool,
+}
+
+impl Cli {
+    /// Parses the command line arguments of the current process.
+    pub(crate) fn parse() -> Result<Cli, String> {
+        Cli::parse_from(std::env::args().skip(1))
+    }
+
+    /// Parses the given command line arguments, excluding the name of the program.
+    ///
+    /// Options taking a value accept it either as the next argument (`--watch 5m`)
+    /// or after an equal sign (`--watch=5m`).
+    pub(crate) fn parse_from(args: impl IntoIterator<Item = String>) -> Result<Cli, String> {
+        let mut cli = Cli::default();
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            let (name, inline_value) = match arg.split_once('=') {
+                Some((name, value)) if name.starts_with("--") => {
+                    (name.to_string(), Some(value.to_string()))
+                }
+                _ => (arg, None),
+            };
+            let mut value = || {
+                inline_value
+                    .clone()
+                    .or_else(|| args.next())
+                    .ok_or_else(|| format!("Missing value for {}", name))
+            };
+            match name.as_str() {
+                "--watch" => cli.watch = Some(parse_interval(&value()?)?),
+                "--strict" => cli.strict = true,
+                "--include-drafts" => cli.include_drafts = true,
+                "--since" => cli.since = Some(parse_since(&value()?)?),
+                "--timeout" => cli.timeout = Some(parse_timeout(&value()?)?),
+                "--issues-file" => cli.issues_file = Some(value()?),
+                "--dump-issues" => cli.dump_issues = Some(value()?),
+                "--profile" => cli.profile = Some(value()?),
+                "--skip-label-check" => cli.skip_label_check = true,
+                "--log-format" => cli.log_format = parse_log_format(&value()?)?,
+                "--check" => cli.check = true,
+                "--list-labels" => cli.list_labels = true,
+                "--init" => cli.init = true,
+                "--force" => cli.force = true,
+                "--emit-schema" => cli.emit_schema = Some(value()?),
+                "-h" | "--help" => cli.help = true,
+                _ => return Err(format!("Unknown argument: {}", name)),
+            }
+        }
+        Ok(cli)
+    }
+}
+
+/// Parses the format of the logs, `text` or `json`.
+fn parse_log_format(value: &str) -> Result<LogFormat, String> {
+    match value.trim() {
+        "text" => Ok(LogFormat::Text),
+        "json" => Ok(LogFormat::Json),
+        _ => Err(format!("Invalid log format: {}", value)),
+    }
+}
+
+/// Checks that the date of `--since` is valid (see `config::parse_date`), and returns it as is.
+fn parse_since(value: &str) -> Result<String, String> {
+    match crate::config::parse_date(value) {
+        Some(_) => Ok(value.trim().to_string()),
+        None => Err(format!("Invalid date: {}", value)),
+    }
+}
+
+/// Parses the time budget of a run, as a positive number of seconds.
+fn parse_timeout(value: &str) -> Result<Duration, String> {
+    match value.trim().parse::<u64>() {
+        Ok(seconds) if seconds > 0 => Ok(Duration::from_secs(seconds)),
+        _ => Err(format!("Invalid timeout: {}", value)),
+    }
+}
+
+/// Parses an interval made of a number and an optional unit (`s`, `m` or `h`), defaulting to minutes.
+fn parse_interval(value: &str) -> Result<Duration, String> {
+    let value = value.trim();
+    let (number, seconds_per_unit) = match value.chars().last() {
+        Some('s') => (&value[..value.len() - 1], 1),
+        Some('m') => (&value[..value.len() - 1], 60),
+        Some('h') => (&value[..value.len() - 1], 60 * 60),
+        _ => (value, 60),
+    };
+    match number.parse::<u64>() {
+        Ok(number) if number > 0 => Ok(Duration::from_secs(number * seconds_per_unit)),
+        _ => Err(format!("Invalid interval: {}", value)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Cli, String> {
+        Cli::parse_from(args.iter().map(|arg| arg.to_string()))
+    }
+
+    #[test]
+    fn parses_the_intervals_with_their_unit() {
+        assert_eq!(parse_interval("30s"), Ok(Duration::from_secs(30)));
+        assert_eq!(parse_interval(" 5m "), Ok(Duration::from_secs(5 * 60)));
+        assert_eq!(parse_interval("2h"), Ok(Duration::from_secs(2 * 60 * 60)));
+        // A bare number is in minutes.
+        assert_eq!(parse_interval("10"), Ok(Duration::from_secs(10 * 60)));
+        for invalid in ["0", "0s", "-5m", "5d", "m", ""] {
+            assert_eq!(
+                parse_interval(invalid),
+                Err(format!("Invalid interval: {}", invalid.trim()))
+            );
+        }
+    }
+
+    #[test]
+    fn parses_the_values_after_a_space_or_an_equal_sign() {
+        let cli = parse(&["--watch", "5m", "--strict", "--profile=staging"]).unwrap();
+        assert_eq!(cli.watch, Some(Duration::from_secs(5 * 60)));
+        assert!(cli.strict);
+        assert_eq!(cli.profile.as_deref(), Some("staging"));
+        let cli = parse(&["--watch=30s", "--issues-file", "issues.json"]).unwrap();
+        assert_eq!(cli.watch, Some(Duration::from_secs(30)));
+        assert_eq!(cli.issues_file.as_deref(), Some("issues.json"));
+        assert!(parse(&[]).unwrap().watch.is_none());
+    }
+
+    #[test]
+    fn rejects_the_unknown_arguments_and_the_missing_values() {
+        assert_eq!(
+            parse(&["--watch"]).unwrap_err(),
+            "Missing value for --watch"
+        );
+        assert_eq!(
+            parse(&["--watch", "soon"]).unwrap_err(),
+            "Invalid interval: soon"
+        );
+        assert_eq!(
+            parse(&["--unknown"]).unwrap_err(),
+            "Unknown argument: --unknown"
+        );
+    }
+}
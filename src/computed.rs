@@ -0,0 +1,29 @@
+// This file contains the computation of the fields derived from other fields of the entries.
+
+use crate::config::ComputeOp;
+
+/// This function computes the value of a computed field from the value of its source field.
+///
+/// ## Arguments
+/// - `op`: The operation computing the value.
+/// - `value`: The value of the source field.
+///
+/// ## Returns
+/// The computed value, or `None` if it cannot be computed (e.g. the source field is not a URL for `host`,
+/// or is empty for `initial`).
+pub(crate) fn compute(op: ComputeOp, value: &str) -> Option<String> {
+    match op {
+        ComputeOp::Host => reqwest::Url::parse(value.trim())
+            .ok()?
+            .host_str()
+            .map(|host| host.trim_start_matches("www.").to_string()),
+        ComputeOp::Lowercase => Some(value.to_lowercase()),
+        ComputeOp::Uppercase => Some(value.to_uppercase()),
+        ComputeOp::Initial => value
+            .trim_start()
+            .chars()
+            .next()
+            .map(|initial| initial.to_uppercase().collect()),
+        ComputeOp::Trim => Some(value.trim().to_string()),
+    }
+}
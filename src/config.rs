@@ -1,16 +1,31 @@
-use serde::Deserialize;
+use serde::{Deserialize, Deserializer};
+use std::collections::{BTreeMap, HashSet};
+use std::fmt;
 
 /// The structure of the script configuration.
 ///
-/// It contains two parts:
+/// It contains the following parts:
 /// - `github`: Configuration for GitHub API access.
-/// - `generation`: Configuration for the data generation process.
+/// - `generation`: Configuration for the data generation process, optional.
+/// - `output`: Configuration for the generated output files, optional.
+/// - `network`: Configuration for the network requests, optional.
+/// - `link_check`: Configuration for the checking of the entry links, optional.
+/// - `webhook`: Configuration for the webhook notified after a successful run, optional.
 /// - `groups`: Configuration for the groups that separate issues and generated data into different categories.
 #[derive(Deserialize)]
-pub(crate) struct Config {
-    pub(crate) github: GithubConfig,
-    pub(crate) generation: GenerationConfig,
-    pub(crate) groups: Vec<GroupConfig>,
+pub struct Config {
+    pub github: GithubConfig,
+    #[serde(default)]
+    pub generation: GenerationConfig,
+    #[serde(default)]
+    pub output: OutputConfig,
+    #[serde(default)]
+    pub network: NetworkConfig,
+    #[serde(default)]
+    pub link_check: LinkCheckConfig,
+    #[serde(default)]
+    pub webhook: WebhookConfig,
+    pub groups: Vec<GroupConfig>,
 }
 
 /// The structure of the GitHub configuration.
@@ -18,32 +33,1356 @@ pub(crate) struct Config {
 /// It contains:
 /// - `owner`: The owner of the GitHub repository where issues to be processed are located.
 /// - `repository`: The name of the GitHub repository where issues to be processed are located.
+/// - `api_url`: The base URL of the GitHub API, defaults to `https://api.github.com`.
+///   Can be changed to point to a GitHub Enterprise Server or a mock server.
+/// - `auth`: How the script authenticates to the GitHub API, defaults to `token`
+///   (the `GITHUB_TOKEN` environment variable).
+/// - `app_id`: The ID of the GitHub App, required when `auth` is `app`.
+/// - `private_key_path`: The path to the PEM private key of the GitHub App, required when `auth` is `app`.
+/// - `installation_id`: The ID of the installation of the GitHub App, looked up from the repository if not set.
+/// - `check_labels`: Whether to warn at startup about the configured labels that do not exist on the repository,
+///   defaults to `true`. It can also be disabled with the `--skip-label-check` command line flag.
+/// - `issues_file`: A JSON file of issues, as returned by the GitHub API, read instead of fetching the issues
+///   (e.g. for offline runs, or to reproduce a problem from a saved response), not set by default.
+///   No token is needed, and the labels are not checked. It can also be set with the `--issues-file <file>`
+///   command line option.
 #[derive(Deserialize)]
-pub(crate) struct GithubConfig {
-    pub(crate) owner: String,
-    pub(crate) repository: String,
+pub struct GithubConfig {
+    pub owner: String,
+    pub repository: String,
+    #[serde(default = "default_api_url")]
+    pub api_url: String,
+    #[serde(default)]
+    pub auth: AuthMode,
+    pub app_id: Option<u64>,
+    pub private_key_path: Option<String>,
+    pub installation_id: Option<u64>,
+    #[serde(default = "default_check_labels")]
+    pub check_labels: bool,
+    pub issues_file: Option<String>,
+}
+
+/// How the script authenticates to the GitHub API.
+#[derive(Deserialize, Clone, Copy, Debug, Default, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum AuthMode {
+    /// Use the token of the `GITHUB_TOKEN` environment variable.
+    #[default]
+    Token,
+    /// Use an installation token of a GitHub App, minted from its private key.
+    App,
+}
+
+/// The default base URL of the GitHub API.
+fn default_api_url() -> String {
+    "https://api.github.com".to_string()
+}
+
+/// The default of whether the configured labels are checked against the labels of the repository.
+fn default_check_labels() -> bool {
+    true
 }
 
 /// The structure of the data generation configuration.
 ///
 /// It contains:
-/// - `label`: The label added to the issues to be included in the generated data.
-/// - `sort_by_updated_time`: Whether to sort the issues by their updated time or creation time.
+/// - `label`: The label, or list of labels, added to the issues to be included in the generated data,
+///   defaults to `active`.
+/// - `label_match`: Whether an issue must carry `all` of the labels or `any` of them to be included,
+///   defaults to `all`.
+/// - `exclude_labels`: The labels that exclude an issue from the generated data even if it is active,
+///   matched case-sensitively, defaults to an empty list.
+/// - `case_insensitive_labels`: Whether to match the labels of the issues with the configured labels
+///   ignoring case (e.g. `friend` matches `Friend`), defaults to `false` (the labels are matched exactly).
+/// - `allowed_authors`: The GitHub logins of the only users whose issues are included in the generated data,
+///   matched case-insensitively, defaults to an empty list (the issues of all the users are included).
+/// - `sort_by`: Whether to sort the entries by `time` or alphabetically `by_field`, defaults to `time`.
+/// - `sort_field`: The field of the entries sorted alphabetically when `sort_by` is `by_field`,
+///   defaults to `name`.
+/// - `sort_by_updated_time`: Whether to sort the issues by their updated time or creation time,
+///   defaults to `false` (i.e. sort by creation time).
+/// - `descending`: Whether to sort the issues from the newest to the oldest (or from Z to A when sorting
+///   by field), defaults to `true`.
+/// - `max_body_length`: The maximum length of the issue bodies, in bytes, the issues with a longer body
+///   are rejected without being parsed, not set by default (the bodies of any length are parsed).
+/// - `input_format`: The format of the entry data in the issue bodies, a `json` code block or the fields
+///   of an issue `form`, defaults to `json`.
+/// - `form_fields`: The headings of the fields of the issue form, mapped to the names of the entry fields
+///   (e.g. `{ "Website Name" = "name" }`), used when `input_format` is `form`, defaults to an empty table.
+/// - `json_attachments`: Whether to use the JSON file attached to an issue as its entry data, when the body
+///   has no data comments, defaults to `false`. Only the attachments uploaded to `github.com` are downloaded.
+/// - `lenient_json`: Whether to accept comments and trailing commas in the JSON code blocks of the issues,
+///   defaults to `false`.
+/// - `allow_other_content`: Whether to accept other Markdown content and code blocks between the data comments,
+///   using the first JSON code block containing valid data, defaults to `false`.
+/// - `duplicate_key`: The field of the entries used to detect duplicate submissions, defaults to `url`.
+/// - `on_duplicate`: What to do with duplicate submissions, defaults to `keep_all`.
+/// - `identity_field`: The field of the entries identifying them both for the duplicate detection (including
+///   the static entries) and in the changes report, replacing `duplicate_key` and `output.changes_key`,
+///   not set by default (each of them uses its own identity, i.e. the issue ID for the changes report by default).
+/// - `rename_fields`: The fields of the submitted entry data to rename, as a table mapping the submitted name
+///   to the name in the generated data (e.g. `{ site = "name", link = "url" }`), defaults to an empty table.
+/// - `keep_fields`: The only fields kept in the entry data after renaming, defaults to `[]` (all the fields are kept).
+/// - `title_field`: The field of the entry set to the title of the issue when it is missing from the entry data
+///   (e.g. `name` for the legacy submissions with the name of the site in the title), not set by default.
+/// - `field_defaults`: The default values of the fields missing from the entry data, as a table,
+///   defaults to an empty table.
+/// - `draft_label`: The label marking the entries that are only included in the generated data
+///   when `include_drafts` is enabled (e.g. for a staging build), not set by default.
+/// - `include_drafts`: Whether to include the draft entries, tagged with a `"draft": true` field,
+///   defaults to `false`. It can also be enabled with the `--include-drafts` command line flag.
+/// - `fail_on_ungrouped`: Whether to fail the run (without writing the output files) if any active entry
+///   matches none of the groups, defaults to `false` (such entries are only logged).
+/// - `min_entries`: The minimum number of active entries, below which the run fails (without writing
+///   the output files), e.g. after the generation label was renamed, defaults to `0` (never fail).
+/// - `strict`: Whether to fail the run (without writing the output files) if any issue is rejected,
+///   defaults to `false`. It can also be enabled with the `--strict` command line flag.
+/// - `since`: If set, only the issues created at or after this date (or updated, when `sort_by_updated_time`
+///   is enabled) are processed, as an RFC 3339 date and time or a `YYYY-MM-DD` date (midnight UTC),
+///   not set by default. It can also be set with the `--since <date>` command line option.
+/// - `incremental`: Whether to only fetch the issues changed since the last run, merging them into
+///   the cached issues, defaults to `false`.
+/// - `cache_file`: The file where the issues are cached for the incremental generation,
+///   defaults to `.cache/issues.json`.
+/// - `static_entries_file`: A JSON file listing entries that do not come from issues (e.g. legacy links),
+///   added to the groups listed in their `static_label_field` field, not set by default.
+/// - `static_label_field`: The field of the static entries listing the labels of their groups,
+///   defaults to `labels`.
+/// - `static_precedence`: Which entry is kept when a static entry and an entry of an issue have the same
+///   value for the `duplicate_key` field, the `issue` one or the `static` one, defaults to `issue`.
+/// - `group_by`: How the entries are grouped in the generated data, by the `labels` of the configured groups
+///   or by the `milestone` of their issues, defaults to `labels`.
+/// - `check_edits`: Whether to flag the active entries whose issue was updated after the generation label
+///   was added, for a new review, defaults to `false`. The time of the label is read from the timeline of
+///   every issue updated after its creation, so it costs one more API request per such issue.
+/// - `withhold_edited`: Whether to withhold the flagged entries from the generated data until they are
+///   approved again, instead of only reporting them, defaults to `false`.
+#[derive(Deserialize)]
+#[serde(default)]
+pub struct GenerationConfig {
+    #[serde(deserialize_with = "deserialize_labels")]
+    pub label: Vec<String>,
+    pub label_match: LabelMatch,
+    pub exclude_labels: Vec<String>,
+    pub case_insensitive_labels: bool,
+    pub allowed_authors: Vec<String>,
+    pub max_body_length: Option<usize>,
+    pub input_format: InputFormat,
+    pub form_fields: BTreeMap<String, String>,
+    pub json_attachments: bool,
+    pub lenient_json: bool,
+    pub allow_other_content: bool,
+    pub sort_by: SortMode,
+    pub sort_field: String,
+    pub sort_by_updated_time: bool,
+    pub descending: bool,
+    pub duplicate_key: String,
+    pub on_duplicate: DuplicatePolicy,
+    pub identity_field: Option<String>,
+    pub rename_fields: BTreeMap<String, String>,
+    pub keep_fields: Vec<String>,
+    pub title_field: Option<String>,
+    pub field_defaults: BTreeMap<String, serde_json::Value>,
+    pub draft_label: Option<String>,
+    pub include_drafts: bool,
+    pub fail_on_ungrouped: bool,
+    pub min_entries: usize,
+    pub strict: bool,
+    #[serde(deserialize_with = "deserialize_date")]
+    pub since: Option<String>,
+    pub incremental: bool,
+    pub cache_file: String,
+    pub static_entries_file: Option<String>,
+    pub static_label_field: String,
+    pub static_precedence: StaticPrecedence,
+    pub group_by: GroupBy,
+    pub check_edits: bool,
+    pub withhold_edited: bool,
+}
+
+impl Default for GenerationConfig {
+    fn default() -> Self {
+        GenerationConfig {
+            label: vec!["active".to_string()],
+            label_match: LabelMatch::All,
+            exclude_labels: Vec::new(),
+            case_insensitive_labels: false,
+            allowed_authors: Vec::new(),
+            max_body_length: None,
+            input_format: InputFormat::Json,
+            form_fields: BTreeMap::new(),
+            json_attachments: false,
+            lenient_json: false,
+            allow_other_content: false,
+            sort_by: SortMode::Time,
+            sort_field: "name".to_string(),
+            sort_by_updated_time: false,
+            descending: true,
+            duplicate_key: "url".to_string(),
+            on_duplicate: DuplicatePolicy::KeepAll,
+            identity_field: None,
+            rename_fields: BTreeMap::new(),
+            keep_fields: Vec::new(),
+            title_field: None,
+            field_defaults: BTreeMap::new(),
+            draft_label: None,
+            include_drafts: false,
+            fail_on_ungrouped: false,
+            min_entries: 0,
+            strict: false,
+            since: None,
+            incremental: false,
+            cache_file: ".cache/issues.json".to_string(),
+            static_entries_file: None,
+            static_label_field: "labels".to_string(),
+            static_precedence: StaticPrecedence::Issue,
+            group_by: GroupBy::Labels,
+            check_edits: false,
+            withhold_edited: false,
+        }
+    }
+}
+
+/// How a list of labels is matched against the labels of an issue.
+#[derive(Deserialize, Clone, Copy, Debug, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum LabelMatch {
+    /// The issue must carry all the labels.
+    All,
+    /// The issue must carry at least one of the labels.
+    Any,
+}
+
+impl LabelMatch {
+    /// Returns whether an issue matches the required labels under this mode,
+    /// where `has_label` tells whether the issue carries a given label.
+    pub fn matches(self, required: &[String], has_label: impl Fn(&str) -> bool) -> bool {
+        match self {
+            LabelMatch::All => required.iter().all(|label| has_label(label)),
+            LabelMatch::Any => required.iter().any(|label| has_label(label)),
+        }
+    }
+}
+
+/// The format of the entry data in the issue bodies.
+#[derive(Deserialize, Clone, Copy, Debug, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum InputFormat {
+    /// A `json` code block between the `<!-- DATA_START -->` and `<!-- DATA_END -->` comments.
+    Json,
+    /// The fields of a GitHub Issue Form, rendered as `### Heading` sections.
+    Form,
+}
+
+/// How the entries are sorted within each group.
+#[derive(Deserialize, Clone, Copy, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum SortMode {
+    /// Sort the entries by the creation or updated time of their issues.
+    Time,
+    /// Sort the entries alphabetically by a field, ignoring case,
+    /// with the entries missing the field last.
+    ByField,
+}
+
+/// What to do with entries that have the same value for the duplicate key.
+#[derive(Deserialize, Clone, Copy, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum DuplicatePolicy {
+    /// Keep all the duplicate entries, only logging the collisions.
+    KeepAll,
+    /// Keep only the most recently updated entry.
+    KeepLatest,
+    /// Keep only the earliest submitted entry, and reject the later submissions.
+    Reject,
+}
+
+/// Which entry is kept when a static entry and an entry of an issue are duplicates.
+#[derive(Deserialize, Clone, Copy, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum StaticPrecedence {
+    /// Keep the entry of the issue, dropping the static entry.
+    Issue,
+    /// Keep the static entry, dropping the entry of the issue.
+    Static,
+}
+
+/// How the entries are grouped in the generated data.
+#[derive(Deserialize, Clone, Copy, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum GroupBy {
+    /// Group the entries by the labels of the configured groups.
+    Labels,
+    /// Group the entries by the milestone of their issues, in the order of the due dates of the milestones,
+    /// ignoring the configured groups.
+    Milestone,
+}
+
+/// Deserializes a label field that can either be a single string or a list of strings.
+fn deserialize_labels<'de, D>(deserializer: D) -> Result<Vec<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum OneOrMany {
+        One(String),
+        Many(Vec<String>),
+    }
+
+    Ok(match OneOrMany::deserialize(deserializer)? {
+        OneOrMany::One(label) => vec![label],
+        OneOrMany::Many(labels) => labels,
+    })
+}
+
+/// Deserializes an optional date field that can either be a string or a TOML date,
+/// since an unquoted date (e.g. `2025-01-01`) is parsed as a TOML date, and keeps it as a string.
+fn deserialize_date<'de, D>(deserializer: D) -> Result<Option<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    match toml::Value::deserialize(deserializer)? {
+        toml::Value::String(date) => Ok(Some(date)),
+        toml::Value::Datetime(date) => Ok(Some(date.to_string())),
+        other => Err(serde::de::Error::custom(format!(
+            "invalid type: {}, expected a date",
+            other.type_str()
+        ))),
+    }
+}
+
+/// Parses a date given as an RFC 3339 date and time (e.g. `2025-01-01T00:00:00Z`)
+/// or as a `YYYY-MM-DD` date, which is then midnight UTC.
+///
+/// ## Returns
+/// The parsed date, or `None` if it is not in one of these formats.
+pub fn parse_date(date: &str) -> Option<chrono::DateTime<chrono::Utc>> {
+    let date = date.trim();
+    chrono::DateTime::parse_from_rfc3339(date)
+        .map(|date| date.with_timezone(&chrono::Utc))
+        .ok()
+        .or_else(|| {
+            chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d")
+                .ok()
+                .map(|date| date.and_time(chrono::NaiveTime::MIN).and_utc())
+        })
+}
+
+/// The structure of the output configuration.
+///
+/// It contains:
+/// - `directory`: The directory where the generated files are written, defaults to `output`.
+///   Note that the files written by the previous run that are not generated anymore are removed from it,
+///   and that it must not contain other files before the first run.
+/// - `formats`: The formats of the generated data files, all rendered from the same generated data in one run,
+///   among `json`, `js` and `yaml`, defaults to `["json", "js"]`.
+/// - `json_file`: The name of the generated JSON file, defaults to `linksData.json`.
+/// - `js_file`: The name of the generated JavaScript module file, defaults to `linksData.mjs`.
+/// - `yaml_file`: The name of the generated YAML file, defaults to `linksData.yaml`.
+/// - `pretty`: Whether to pretty-print the generated JSON file, defaults to `true`.
+/// - `minify_js`: Whether to write the JavaScript object of the generated module on a single line,
+///   instead of pretty-printing it, defaults to `false`.
+/// - `indent`: The indentation of the pretty-printed JSON file and JavaScript module, as a number of spaces
+///   or a literal string of spaces and tabs (e.g. `"\t"`), defaults to `2`.
+/// - `large_integer_fields`: How the integers beyond `Number.MAX_SAFE_INTEGER` (2^53 - 1), which lose precision
+///   as JavaScript numbers, are written in the JavaScript module, as a table mapping the field name to
+///   `bigint` (e.g. `123n`) or `string` (e.g. `"123"`), defaults to an empty table.
+/// - `float_decimals`: The number of decimals of the floating-point numbers of the JavaScript module
+///   (e.g. `2` writes `1.0` as `1.00`), the integers being kept as they are, not set by default
+///   (the floating-point numbers are written with the shortest decimals, keeping a `.0` for the whole ones).
+/// - `escape_script_close`: Whether to escape the `</` sequences of the strings of the JavaScript module
+///   as `<\/`, so that it can be inlined in an HTML `<script>` element, defaults to `true`.
+/// - `write_summary`: Whether to write a machine-readable summary of the run to the output directory,
+///   defaults to `false`.
+/// - `summary_file`: The name of the summary file, defaults to `summary.json`.
+/// - `write_changes`: Whether to write a report of the entries added, removed and updated since the previous
+///   JSON file of the output directory, defaults to `false`.
+/// - `changes_file`: The name of the report of the changed entries, defaults to `changes.json`.
+/// - `changes_key`: The field of the entries identifying them across runs in the report, not set by default
+///   (the entries are identified by the ID of their issue).
+/// - `write_preview`: Whether to write an HTML preview of the generated data to the output directory,
+///   for reviewing changes, defaults to `false`.
+/// - `preview_file`: The name of the HTML preview file, defaults to `preview.html`.
+/// - `write_feed`: Whether to write an Atom feed of the newest entries to the output directory, defaults to `false`.
+/// - `feed_file`: The name of the Atom feed file, defaults to `feed.xml`.
+/// - `feed_limit`: The maximum number of entries in the feed, defaults to `20`.
+/// - `feed_title`: The title of the feed, defaults to `New Friend Links`.
+/// - `feed_link`: The link of the feed (e.g. the friend links page), defaults to the URL of the GitHub repository.
+/// - `write_metrics`: Whether to write the counts of the summary as Prometheus metrics to the output directory,
+///   defaults to `false`.
+/// - `metrics_file`: The name of the metrics file, defaults to `metrics.prom`.
+/// - `write_flat`: Whether to also write a flat list of all the entries, each with the label of its group,
+///   to the output directory, defaults to `false`.
+/// - `flat_file`: The name of the flat list file, defaults to `linksDataFlat.json`.
+/// - `flat_group_key`: The key of the group label added to the entries of the flat list, defaults to `group`.
+/// - `flat_group_array`: Whether an entry in several groups appears only once in the flat list, with the list
+///   of the labels of its groups, defaults to `false` (it appears once per group).
+/// - `include_timestamps`: Whether to add the creation and updated time of the issue (as RFC 3339 strings)
+///   to each entry, defaults to `false`. A same-named field supplied by the contributor is kept as is.
+/// - `created_at_key`: The key of the creation time field added to each entry, defaults to `createdAt`.
+/// - `updated_at_key`: The key of the updated time field added to each entry, defaults to `updatedAt`.
+/// - `include_group_color`: Whether to add the color of the GitHub label of every group (e.g. `#ff0000`)
+///   to the group objects, when it is known, defaults to `false`.
+/// - `include_issue`: Whether to add the number (`issueNumber`) and the URL (`issueUrl`) of the issue
+///   submitting the entry to each entry, defaults to `false`. A same-named field supplied by the contributor
+///   is kept as is.
+/// - `include_empty_groups`: Whether to emit the groups (and child groups) that have no entries,
+///   with an empty `entries` list, defaults to `true`.
+/// - `group_order_file`: A text file listing the primary labels of the groups, one per line, in the order
+///   of the groups in the generated data, not set by default (the groups are in the order of the configuration).
+///   The groups not listed in the file follow in the order of the configuration.
+/// - `include_slug`: Whether to add a unique `slug` field to each entry, for anchor links, defaults to `false`.
+/// - `slug_field`: The field of the entry the slug is generated from, defaults to `url`.
+///   If the field contains a URL, the slug is generated from its host.
+/// - `label_flags`: The boolean fields added to each entry from the labels of its issue, as a table mapping
+///   the label name to the field name (e.g. `{ featured = "featured" }`), defaults to an empty table.
+///   The field is `true` if the issue carries the label and `false` otherwise.
+/// - `computed_fields`: The fields added to each entry, computed from another field of the entry,
+///   as a table mapping the name of the field to its `source` field and operation `op`
+///   (e.g. `{ domain = { source = "url", op = "host" } }`), defaults to an empty table.
+/// - `avatar_field`: The field of the entry containing the avatar URL, defaults to `avatar`.
+/// - `default_avatar`: The avatar URL used for the entries whose avatar field is missing or empty,
+///   not set by default (the entries are kept as they are).
+/// - `resolve_relative_avatar`: Whether to resolve a relative avatar URL (e.g. `/avatar.png`)
+///   against the origin of the `url` field of the entry, defaults to `false`.
+/// - `strip_fields`: The top-level fields of the entries removed before they reach the output
+///   (e.g. internal notes of the contributors), defaults to `[]`.
+/// - `strip_field_prefixes`: The prefixes of the top-level fields of the entries removed before they
+///   reach the output (e.g. `_` for all the fields starting with `_`), defaults to `[]`.
+/// - `sanitize_fields`: The top-level string fields of the entries to sanitize before they reach the output,
+///   removing the control characters and handling the HTML content according to `sanitize_mode`,
+///   defaults to `[]` (no sanitization).
+/// - `sanitize_mode`: How the HTML content of the sanitized fields is handled, defaults to `escape`.
+/// - `validate_output`: Whether to check that the generated data has the shape expected by the website
+///   before writing it, failing the run otherwise, defaults to `false`.
+/// - `required_entry_fields`: The fields every entry of the generated data must have (as non-empty strings)
+///   when `validate_output` is enabled, defaults to `["name", "url"]`.
+/// - `key_case`: The naming convention all the keys of the generated data (the envelope, the groups
+///   and the entries, recursively) are converted to, `camel_case`, `snake_case` or `kebab_case`,
+///   not set by default (the keys are kept as they are).
+/// - `keys`: The key names of the group objects in the generated data, defaults to
+///   `group`, `groupName`, `groupDesc`, `entries` and `children`.
+/// - `envelope`: Whether to wrap the groups in an object with the generation time (`generatedAt`)
+///   and the script version (`version`), instead of writing a bare array, defaults to `false`.
+/// - `envelope_key`: The key of the groups in the envelope object, defaults to `groups`.
+/// - `destinations`: The additional files the generated data is also written to, each with its own format,
+///   path and key names, defaults to an empty list.
+/// - `max_output_bytes`: The maximum size of every generated data file (the files of the `formats`,
+///   the flat list and the destinations), in bytes, not set by default (no limit).
+/// - `on_output_too_large`: What to do when a generated data file is larger than `max_output_bytes`,
+///   `warn` or `fail` the run (without writing the output files), defaults to `warn`.
+#[derive(Deserialize)]
+#[serde(default)]
+pub struct OutputConfig {
+    pub directory: String,
+    pub formats: Vec<OutputFormat>,
+    pub json_file: String,
+    pub js_file: String,
+    pub yaml_file: String,
+    pub pretty: bool,
+    pub minify_js: bool,
+    pub indent: Indent,
+    pub large_integer_fields: BTreeMap<String, LargeIntegerMode>,
+    pub float_decimals: Option<usize>,
+    pub escape_script_close: bool,
+    pub write_summary: bool,
+    pub summary_file: String,
+    pub write_changes: bool,
+    pub changes_file: String,
+    pub changes_key: Option<String>,
+    pub write_preview: bool,
+    pub preview_file: String,
+    pub write_feed: bool,
+    pub feed_file: String,
+    pub feed_limit: usize,
+    pub feed_title: String,
+    pub feed_link: Option<String>,
+    pub write_metrics: bool,
+    pub metrics_file: String,
+    pub write_flat: bool,
+    pub flat_file: String,
+    pub flat_group_key: String,
+    pub flat_group_array: bool,
+    pub include_timestamps: bool,
+    pub created_at_key: String,
+    pub updated_at_key: String,
+    pub include_issue: bool,
+    pub include_group_color: bool,
+    pub include_empty_groups: bool,
+    pub group_order_file: Option<String>,
+    pub include_slug: bool,
+    pub slug_field: String,
+    pub label_flags: BTreeMap<String, String>,
+    pub computed_fields: BTreeMap<String, ComputedField>,
+    pub avatar_field: String,
+    pub default_avatar: Option<String>,
+    pub resolve_relative_avatar: bool,
+    pub strip_fields: Vec<String>,
+    pub strip_field_prefixes: Vec<String>,
+    pub sanitize_fields: Vec<String>,
+    pub sanitize_mode: SanitizeMode,
+    pub validate_output: bool,
+    pub required_entry_fields: Vec<String>,
+    pub key_case: Option<KeyCase>,
+    pub keys: OutputKeys,
+    pub envelope: bool,
+    pub envelope_key: String,
+    pub destinations: Vec<OutputDestination>,
+    pub max_output_bytes: Option<usize>,
+    pub on_output_too_large: SizeLimitMode,
+}
+
+impl OutputConfig {
+    /// Returns the name of the generated data file of a format in the output directory.
+    pub fn format_file(&self, format: OutputFormat) -> &str {
+        match format {
+            OutputFormat::Json => &self.json_file,
+            OutputFormat::Js => &self.js_file,
+            OutputFormat::Yaml => &self.yaml_file,
+        }
+    }
+}
+
+impl Default for OutputConfig {
+    fn default() -> Self {
+        OutputConfig {
+            directory: "output".to_string(),
+            formats: vec![OutputFormat::Json, OutputFormat::Js],
+            json_file: "linksData.json".to_string(),
+            js_file: "linksData.mjs".to_string(),
+            yaml_file: "linksData.yaml".to_string(),
+            pretty: true,
+            minify_js: false,
+            indent: Indent::Spaces(2),
+            large_integer_fields: BTreeMap::new(),
+            float_decimals: None,
+            escape_script_close: true,
+            write_summary: false,
+            summary_file: "summary.json".to_string(),
+            write_changes: false,
+            changes_file: "changes.json".to_string(),
+            changes_key: None,
+            write_preview: false,
+            preview_file: "preview.html".to_string(),
+            write_feed: false,
+            feed_file: "feed.xml".to_string(),
+            feed_limit: 20,
+            feed_title: "New Friend Links".to_string(),
+            feed_link: None,
+            write_metrics: false,
+            metrics_file: "metrics.prom".to_string(),
+            write_flat: false,
+            flat_file: "linksDataFlat.json".to_string(),
+            flat_group_key: "group".to_string(),
+            flat_group_array: false,
+            include_timestamps: false,
+            created_at_key: "createdAt".to_string(),
+            updated_at_key: "updatedAt".to_string(),
+            include_issue: false,
+            include_group_color: false,
+            include_empty_groups: true,
+            group_order_file: None,
+            include_slug: false,
+            slug_field: "url".to_string(),
+            label_flags: BTreeMap::new(),
+            computed_fields: BTreeMap::new(),
+            avatar_field: "avatar".to_string(),
+            default_avatar: None,
+            resolve_relative_avatar: false,
+            strip_fields: Vec::new(),
+            strip_field_prefixes: Vec::new(),
+            sanitize_fields: Vec::new(),
+            sanitize_mode: SanitizeMode::Escape,
+            validate_output: false,
+            required_entry_fields: vec!["name".to_string(), "url".to_string()],
+            key_case: None,
+            keys: OutputKeys::default(),
+            envelope: false,
+            envelope_key: "groups".to_string(),
+            destinations: Vec::new(),
+            max_output_bytes: None,
+            on_output_too_large: SizeLimitMode::Warn,
+        }
+    }
+}
+
+/// What to do when a generated data file is larger than the configured limit.
+#[derive(Deserialize, Clone, Copy, Debug, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum SizeLimitMode {
+    /// Print a warning, and write the files anyway.
+    Warn,
+    /// Fail the run, without writing the output files.
+    Fail,
+}
+
+/// The indentation of the pretty-printed output files.
+#[derive(Deserialize, Clone, Debug, PartialEq)]
+#[serde(untagged)]
+pub enum Indent {
+    /// A number of spaces (e.g. `4`).
+    Spaces(usize),
+    /// A literal string (e.g. `"\t"`).
+    Literal(String),
+}
+
+impl Indent {
+    /// Returns the string written for one level of indentation.
+    pub fn as_string(&self) -> String {
+        match self {
+            Indent::Spaces(count) => " ".repeat(*count),
+            Indent::Literal(indent) => indent.clone(),
+        }
+    }
+}
+
+/// An additional file the generated data is written to.
+///
+/// It contains:
+/// - `format`: The format of the file, `json`, `js` (a JavaScript module, like `js_file`) or `yaml`.
+/// - `path`: The path of the file, relative to the current directory. Its parent directories are created if needed.
+/// - `keys`: The key names of the group objects in this file, defaults to the `[output.keys]` of the main files.
+/// - `key_case`: The naming convention of the keys of this file, defaults to the `key_case` of the main files.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct OutputDestination {
+    pub format: OutputFormat,
+    pub path: String,
+    pub keys: Option<OutputKeys>,
+    pub key_case: Option<KeyCase>,
+}
+
+/// The format of a file the generated data is written to.
+#[derive(Deserialize, Clone, Copy, Debug, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputFormat {
+    /// A JSON file, pretty-printed if `output.pretty` is enabled.
+    Json,
+    /// A JavaScript module exporting the data as its default export.
+    Js,
+    /// A YAML document, e.g. for the static site generators reading their data files in YAML.
+    Yaml,
+}
+
+/// The naming convention the keys of the generated data are converted to.
+#[derive(Deserialize, Clone, Copy, Debug, PartialEq)]
+pub enum KeyCase {
+    /// E.g. `groupName`.
+    #[serde(rename = "camel_case")]
+    Camel,
+    /// E.g. `group_name`.
+    #[serde(rename = "snake_case")]
+    Snake,
+    /// E.g. `group-name`.
+    #[serde(rename = "kebab_case")]
+    Kebab,
+}
+
+/// The key of the generation time in the envelope object of the generated data.
+pub const ENVELOPE_GENERATED_AT_KEY: &str = "generatedAt";
+/// The key of the script version in the envelope object of the generated data.
+pub const ENVELOPE_VERSION_KEY: &str = "version";
+
+/// The key names of the group objects in the generated data.
+///
+/// When the `[output.keys]` table is set, all the keys except `children` must be listed.
+/// It contains:
+/// - `group`: The key of the primary label of the group, defaults to `group`.
+/// - `group_name`: The key of the name of the group, defaults to `groupName`.
+/// - `group_desc`: The key of the description of the group, defaults to `groupDesc`.
+/// - `entries`: The key of the list of entries of the group, defaults to `entries`.
+/// - `children`: The key of the list of child groups of the group, defaults to `children`.
+/// - `group_color`: The key of the color of the label of the group, defaults to `groupColor`.
+#[derive(Deserialize, Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct OutputKeys {
+    pub group: String,
+    pub group_name: String,
+    pub group_desc: String,
+    pub entries: String,
+    #[serde(default = "default_children_key")]
+    pub children: String,
+    #[serde(default = "default_group_color_key")]
+    pub group_color: String,
+}
+
+impl OutputKeys {
+    /// Returns all the key names, with the name of their field.
+    pub fn all(&self) -> [(&'static str, &str); 6] {
+        [
+            ("group", &self.group),
+            ("group_name", &self.group_name),
+            ("group_desc", &self.group_desc),
+            ("entries", &self.entries),
+            ("children", &self.children),
+            ("group_color", &self.group_color),
+        ]
+    }
+}
+
+impl Default for OutputKeys {
+    fn default() -> Self {
+        OutputKeys {
+            group: "group".to_string(),
+            group_name: "groupName".to_string(),
+            group_desc: "groupDesc".to_string(),
+            entries: "entries".to_string(),
+            children: default_children_key(),
+            group_color: default_group_color_key(),
+        }
+    }
+}
+
+/// The default key of the list of child groups of a group.
+fn default_children_key() -> String {
+    "children".to_string()
+}
+
+/// The default key of the color of the label of a group.
+fn default_group_color_key() -> String {
+    "groupColor".to_string()
+}
+
+/// A field added to each entry, computed from another field of the entry.
+///
+/// It contains:
+/// - `source`: The top-level string field of the entry the value is computed from.
+/// - `op`: The operation computing the value from the source field.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ComputedField {
+    pub source: String,
+    pub op: ComputeOp,
+}
+
+/// The operations computing a field from another field of an entry.
+#[derive(Deserialize, Clone, Copy, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ComputeOp {
+    /// The host of the URL in the source field, without a leading `www.` (e.g. `blog.example.com`).
+    Host,
+    /// The source field in lowercase.
+    Lowercase,
+    /// The source field in uppercase.
+    Uppercase,
+    /// The first character of the source field, ignoring the leading whitespace, in uppercase (e.g. `A`).
+    Initial,
+    /// The source field without the leading and trailing whitespace.
+    Trim,
+}
+
+/// How the integers beyond `Number.MAX_SAFE_INTEGER` of a field are written in the JavaScript module.
+#[derive(Deserialize, Clone, Copy, Debug, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum LargeIntegerMode {
+    /// Write the integers as BigInt literals (e.g. `123n`).
+    BigInt,
+    /// Write the integers as strings (e.g. `"123"`).
+    String,
+}
+
+/// How the HTML content of the sanitized fields is handled.
+#[derive(Deserialize, Clone, Copy, Debug, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum SanitizeMode {
+    /// Escape the HTML special characters (e.g. `<` becomes `&lt;`).
+    Escape,
+    /// Remove the HTML tags and any remaining angle bracket.
+    Strip,
+}
+
+/// The headers set by the script on the requests sent to the GitHub API, which `network.headers` cannot override.
+pub const RESERVED_HEADERS: [&str; 7] = [
+    "authorization",
+    "user-agent",
+    "accept",
+    "x-github-api-version",
+    "content-type",
+    "content-length",
+    "host",
+];
+
+/// The structure of the network configuration.
+///
+/// It contains:
+/// - `timeout_seconds`: The timeout of every request sent to the GitHub API, in seconds, defaults to `30`.
+/// - `max_attempts`: The maximum number of attempts of a request failing with a server error (5xx) or a timeout,
+///   defaults to `3`.
+/// - `retry_base_delay_ms`: The delay before the first retry, in milliseconds, doubled after every attempt,
+///   defaults to `1000`.
+/// - `max_rate_limit_wait_seconds`: The maximum time to wait for the rate limit to be reset before retrying,
+///   in seconds, defaults to `300`. The request fails if the rate limit is reset later than that.
+/// - `user_agent`: The `User-Agent` header of every request (to the GitHub API and to the checked links),
+///   e.g. to distinguish several deployments, defaults to the name and version of the script.
+/// - `headers`: The additional headers of every request sent to the GitHub API (e.g. the authentication header
+///   of a corporate gateway), as a table mapping the header name to its value, defaults to an empty table.
+///   The headers set by the script (e.g. `Authorization` or `User-Agent`) cannot be overridden.
+/// - `resume_pages`: Whether to save the progress of the pagination of the issues after every page, so that
+///   a run interrupted in the middle of it (e.g. on a flaky network) resumes after the last fetched page,
+///   defaults to `false`.
+/// - `progress_file`: The file where the progress of the pagination is saved until it is complete,
+///   defaults to `.cache/fetch_progress.json`.
+/// - `resume_window_seconds`: The maximum age of the saved progress, in seconds, after which all the pages
+///   are fetched again since the issues may have changed, defaults to `600`.
 #[derive(Deserialize)]
-pub(crate) struct GenerationConfig {
-    pub(crate) label: String,
-    pub(crate) sort_by_updated_time: bool,
+#[serde(default)]
+pub struct NetworkConfig {
+    pub timeout_seconds: u64,
+    pub max_attempts: u32,
+    pub retry_base_delay_ms: u64,
+    pub max_rate_limit_wait_seconds: u64,
+    pub user_agent: Option<String>,
+    pub headers: BTreeMap<String, String>,
+    pub resume_pages: bool,
+    pub progress_file: String,
+    pub resume_window_seconds: u64,
+}
+
+impl NetworkConfig {
+    /// Returns the `User-Agent` header of the requests: the configured one,
+    /// or the name and version of the script.
+    pub fn user_agent(&self) -> String {
+        match &self.user_agent {
+            Some(user_agent) => user_agent.clone(),
+            None => format!(
+                "blog-friend-links-data-generator/{} by iXOR Technology",
+                env!("CARGO_PKG_VERSION")
+            ),
+        }
+    }
+}
+
+impl Default for NetworkConfig {
+    fn default() -> Self {
+        NetworkConfig {
+            timeout_seconds: 30,
+            max_attempts: 3,
+            retry_base_delay_ms: 1000,
+            max_rate_limit_wait_seconds: 300,
+            user_agent: None,
+            headers: BTreeMap::new(),
+            resume_pages: false,
+            progress_file: ".cache/fetch_progress.json".to_string(),
+            resume_window_seconds: 600,
+        }
+    }
+}
+
+/// The structure of the link checking configuration.
+///
+/// It contains:
+/// - `enabled`: Whether to check that the link of every active entry is reachable, defaults to `false`.
+/// - `url_field`: The field of the entry containing the link to check, defaults to `url`.
+/// - `concurrency`: The maximum number of links checked at the same time, defaults to `8`.
+/// - `per_host_concurrency`: The maximum number of links of the same host (including its subdomains)
+///   checked at the same time, defaults to `2`.
+/// - `timeout_seconds`: The timeout of every request, in seconds, defaults to `10`.
+/// - `max_attempts`: The maximum number of attempts of a request failing with a timeout or a connection error
+///   before the link is declared dead, defaults to `3`.
+/// - `retry_delay_ms`: The delay between two attempts, in milliseconds, defaults to `1000`.
+/// - `check_avatars`: Whether to check that the avatar of every active entry is an actual image, i.e. responds
+///   with an `image/*` `Content-Type`, independently of `enabled`, defaults to `false`.
+/// - `avatar_field`: The field of the entry containing the avatar URL to check, defaults to `avatar`.
+/// - `drop_invalid_avatars`: Whether to drop the entries whose avatar is not an image, as rejected issues,
+///   instead of only printing a warning, defaults to `false`.
+#[derive(Deserialize)]
+#[serde(default)]
+pub struct LinkCheckConfig {
+    pub enabled: bool,
+    pub url_field: String,
+    pub concurrency: usize,
+    pub per_host_concurrency: usize,
+    pub timeout_seconds: u64,
+    pub max_attempts: u32,
+    pub retry_delay_ms: u64,
+    pub check_avatars: bool,
+    pub avatar_field: String,
+    pub drop_invalid_avatars: bool,
+}
+
+impl Default for LinkCheckConfig {
+    fn default() -> Self {
+        LinkCheckConfig {
+            enabled: false,
+            url_field: "url".to_string(),
+            concurrency: 8,
+            per_host_concurrency: 2,
+            timeout_seconds: 10,
+            max_attempts: 3,
+            retry_delay_ms: 1000,
+            check_avatars: false,
+            avatar_field: "avatar".to_string(),
+            drop_invalid_avatars: false,
+        }
+    }
+}
+
+/// The structure of the webhook configuration.
+///
+/// It contains:
+/// - `url`: The URL receiving a POST request with the machine-readable summary of every successful run
+///   (e.g. the rebuild webhook of the website), not set by default.
+/// - `timeout_seconds`: The timeout of the request, in seconds, defaults to `10`.
+#[derive(Deserialize)]
+#[serde(default)]
+pub struct WebhookConfig {
+    pub url: Option<String>,
+    pub timeout_seconds: u64,
+}
+
+impl Default for WebhookConfig {
+    fn default() -> Self {
+        WebhookConfig {
+            url: None,
+            timeout_seconds: 10,
+        }
+    }
 }
 
 /// The structure of a group configuration.
 ///
 /// It contains:
-/// - `name`: The name of the group.
-/// - `description`: The description of the group.
-/// - `label`: The label added to the issues to be included in this group.
+/// - `name`: The name of the group, falls back to the name of the GitHub label if not set.
+/// - `description`: The description of the group, falls back to the description of the GitHub label if not set.
+/// - `label`: The label, or list of labels, added to the issues to be included in this group.
+///   An issue carrying any of the labels is included, and the first label identifies the group in the output.
+/// - `max_entries`: The maximum number of entries of this group in the generated data, keeping the first ones
+///   in the sort order (e.g. the newest ones), not set by default (all the entries are kept).
+/// - `sort`: The sort order of the entries of this group (and of its child groups, whose own sort order
+///   inherits the fields it does not set), overriding the `sort_by`, `sort_field`, `sort_by_updated_time` and
+///   `descending` fields of the generation configuration that it sets, not set by default (the entries are in
+///   the sort order of the parent group, or of the generation for a top-level group).
+/// - `children`: The child groups of this group, whose entries are the entries of this group
+///   that also carry the child group label, defaults to an empty list.
 #[derive(Deserialize)]
-pub(crate) struct GroupConfig {
-    pub(crate) name: String,
-    pub(crate) description: String,
-    pub(crate) label: String,
+pub struct GroupConfig {
+    pub name: Option<String>,
+    pub description: Option<String>,
+    #[serde(deserialize_with = "deserialize_labels")]
+    pub label: Vec<String>,
+    pub max_entries: Option<usize>,
+    pub sort: Option<GroupSort>,
+    #[serde(default)]
+    pub children: Vec<GroupConfig>,
+}
+
+/// The sort order of the entries of a group, each field falling back to the same-named field
+/// of the sort order of the parent group, and then of the generation configuration, when it is not set.
+#[derive(Deserialize, Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct GroupSort {
+    pub sort_by: Option<SortMode>,
+    pub sort_field: Option<String>,
+    pub sort_by_updated_time: Option<bool>,
+    pub descending: Option<bool>,
+}
+
+/// The problems that can be found in the configuration by `Config::validate`.
+#[derive(Debug)]
+pub enum ConfigError {
+    /// The `github.owner` field is empty.
+    EmptyOwner,
+    /// The `github.repository` field is empty.
+    EmptyRepository,
+    /// The `GITHUB_TOKEN` environment variable is not set or empty.
+    MissingToken,
+    /// The `github.app_id` or `github.private_key_path` field is not set while the GitHub App authentication is used.
+    MissingAppCredentials,
+    /// The `generation.label` field is empty.
+    EmptyGenerationLabel,
+    /// The `generation.form_fields` field is empty while the issue form input format is used.
+    EmptyFormFields,
+    /// The identity field with the given name (`generation.identity_field` or `output.changes_key`) is set but empty.
+    EmptyIdentityField(String),
+    /// The `generation.since` field is not a valid date.
+    InvalidSince(String),
+    /// The `label` field of the group with the given name is empty.
+    EmptyGroupLabel(String),
+    /// The given label is used by more than one group.
+    DuplicateGroupLabel(String),
+    /// The output configuration field with the given name is empty.
+    EmptyOutputPath(String),
+    /// The `output.keys` field with the given name is empty.
+    EmptyOutputKey(String),
+    /// The given key name is used by more than one of the `output.keys` fields.
+    DuplicateOutputKey(String),
+    /// The `network.headers` header with the given name is one of the headers set by the script.
+    ReservedHeader(String),
+    /// The `network.headers` header with the given name has an invalid name or value.
+    InvalidHeader(String),
+    /// The `output.indent` field is a string that contains other characters than spaces and tabs.
+    InvalidIndent(String),
+    /// The `output.envelope_key` field is empty or collides with the metadata keys of the envelope.
+    InvalidEnvelopeKey(String),
+    /// The `output.formats` field is empty, so no data file would be generated.
+    NoOutputFormats,
+    /// The report of the changed entries is enabled while the JSON file, which it is compared with, is not generated.
+    ChangesWithoutJson,
+    /// No groups are configured, so no active entry can end up in the generated data.
+    NoGroups,
+    /// None of the generation labels is the label of a group (including the child groups), with the generation labels.
+    GenerationLabelMatchesNoGroup(Vec<String>),
+}
+
+impl ConfigError {
+    /// Returns whether the problem is only a warning, which does not prevent the script from running.
+    pub fn is_warning(&self) -> bool {
+        matches!(
+            self,
+            ConfigError::NoGroups
+                | ConfigError::ChangesWithoutJson
+                | ConfigError::GenerationLabelMatchesNoGroup(_)
+        )
+    }
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigError::EmptyOwner => write!(f, "`github.owner` must not be empty."),
+            ConfigError::EmptyRepository => write!(f, "`github.repository` must not be empty."),
+            ConfigError::MissingToken => {
+                write!(f, "GITHUB_TOKEN environment variable is not set or empty.")
+            }
+            ConfigError::MissingAppCredentials => write!(
+                f,
+                "`github.app_id` and `github.private_key_path` must be set when `github.auth` is `app`."
+            ),
+            ConfigError::EmptyGenerationLabel => {
+                write!(f, "`generation.label` must not be empty.")
+            }
+            ConfigError::EmptyFormFields => write!(
+                f,
+                "`generation.form_fields` must not be empty when `generation.input_format` is `form`."
+            ),
+            ConfigError::EmptyIdentityField(name) => {
+                write!(f, "`{}` must not be empty when set.", name)
+            }
+            ConfigError::InvalidSince(date) => write!(
+                f,
+                "`generation.since` must be an RFC 3339 date and time or a YYYY-MM-DD date, found \"{}\".",
+                date
+            ),
+            ConfigError::EmptyGroupLabel(name) => {
+                write!(f, "The label of group \"{}\" must not be empty.", name)
+            }
+            ConfigError::DuplicateGroupLabel(label) => {
+                write!(f, "The label \"{}\" is used by more than one group.", label)
+            }
+            ConfigError::EmptyOutputPath(field) => {
+                write!(f, "`output.{}` must not be empty.", field)
+            }
+            ConfigError::EmptyOutputKey(field) => {
+                write!(f, "`output.keys.{}` must not be empty.", field)
+            }
+            ConfigError::ReservedHeader(name) => write!(
+                f,
+                "`network.headers` cannot set the \"{}\" header, which is set by the script.",
+                name
+            ),
+            ConfigError::InvalidHeader(name) => write!(
+                f,
+                "`network.headers` has an invalid header \"{}\", the name must be a valid header name and the value must not contain control characters.",
+                name
+            ),
+            ConfigError::InvalidIndent(indent) => write!(
+                f,
+                "`output.indent` must be a number of spaces or a string of spaces and tabs, found {:?}.",
+                indent
+            ),
+            ConfigError::InvalidEnvelopeKey(key) => write!(
+                f,
+                "`output.envelope_key` must not be empty, `generatedAt` or `version`, found \"{}\".",
+                key
+            ),
+            ConfigError::DuplicateOutputKey(key) => write!(
+                f,
+                "The output key \"{}\" is used by more than one of the `output.keys` fields.",
+                key
+            ),
+            ConfigError::NoOutputFormats => {
+                write!(f, "`output.formats` must list at least one format.")
+            }
+            ConfigError::ChangesWithoutJson => write!(
+                f,
+                "`output.write_changes` is enabled but `output.formats` does not include `json`, every entry will be reported as added."
+            ),
+            ConfigError::NoGroups => write!(
+                f,
+                "No groups are configured, entries with the generation label will not be included in any group."
+            ),
+            ConfigError::GenerationLabelMatchesNoGroup(labels) => write!(
+                f,
+                "The generation label ({}) is not the label of any group, the active entries are only included if their issue also carries the label of a group.",
+                labels.join(", ")
+            ),
+        }
+    }
+}
+
+impl Config {
+    /// Checks the configuration for problems and reports all of them at once.
+    ///
+    /// ## Returns
+    /// `Ok(())` if no problem is found, or a vector of all the problems found otherwise,
+    /// some of which may only be warnings (see `ConfigError::is_warning`).
+    pub fn validate(&self) -> Result<(), Vec<ConfigError>> {
+        let mut errors: Vec<ConfigError> = Vec::new();
+
+        if self.github.owner.trim().is_empty() {
+            errors.push(ConfigError::EmptyOwner);
+        }
+        if self.github.repository.trim().is_empty() {
+            errors.push(ConfigError::EmptyRepository);
+        }
+        match self.github.auth {
+            // No credentials are needed to read the issues from a file.
+            _ if self.github.issues_file.is_some() => {}
+            AuthMode::Token => {
+                if std::env::var("GITHUB_TOKEN").map_or(true, |token| token.trim().is_empty()) {
+                    errors.push(ConfigError::MissingToken);
+                }
+            }
+            AuthMode::App => {
+                if self.github.app_id.is_none()
+                    || self
+                        .github
+                        .private_key_path
+                        .as_ref()
+                        .map_or(true, |path| path.trim().is_empty())
+                {
+                    errors.push(ConfigError::MissingAppCredentials);
+                }
+            }
+        }
+
+        if self.generation.label.is_empty()
+            || self
+                .generation
+                .label
+                .iter()
+                .any(|label| label.trim().is_empty())
+        {
+            errors.push(ConfigError::EmptyGenerationLabel);
+        }
+        if self.generation.input_format == InputFormat::Form
+            && self.generation.form_fields.is_empty()
+        {
+            errors.push(ConfigError::EmptyFormFields);
+        }
+        for (name, field) in [
+            ("generation.identity_field", &self.generation.identity_field),
+            ("output.changes_key", &self.output.changes_key),
+        ] {
+            if field.as_ref().is_some_and(|field| field.trim().is_empty()) {
+                errors.push(ConfigError::EmptyIdentityField(name.to_string()));
+            }
+        }
+        if let Some(since) = &self.generation.since {
+            if parse_date(since).is_none() {
+                errors.push(ConfigError::InvalidSince(since.clone()));
+            }
+        }
+
+        for (field, value) in [
+            ("directory", &self.output.directory),
+            ("json_file", &self.output.json_file),
+            ("js_file", &self.output.js_file),
+            ("yaml_file", &self.output.yaml_file),
+            ("summary_file", &self.output.summary_file),
+            ("changes_file", &self.output.changes_file),
+            ("preview_file", &self.output.preview_file),
+            ("feed_file", &self.output.feed_file),
+            ("metrics_file", &self.output.metrics_file),
+            ("flat_file", &self.output.flat_file),
+            ("flat_group_key", &self.output.flat_group_key),
+        ] {
+            if value.trim().is_empty() {
+                errors.push(ConfigError::EmptyOutputPath(field.to_string()));
+            }
+        }
+        for (name, value) in &self.network.headers {
+            if RESERVED_HEADERS.contains(&name.to_lowercase().as_str()) {
+                errors.push(ConfigError::ReservedHeader(name.clone()));
+            } else if reqwest::header::HeaderName::from_bytes(name.as_bytes()).is_err()
+                || reqwest::header::HeaderValue::from_str(value).is_err()
+            {
+                errors.push(ConfigError::InvalidHeader(name.clone()));
+            }
+        }
+        if let Indent::Literal(indent) = &self.output.indent {
+            if !indent.chars().all(|c| c == ' ' || c == '\t') {
+                errors.push(ConfigError::InvalidIndent(indent.clone()));
+            }
+        }
+        if self.output.formats.is_empty() {
+            errors.push(ConfigError::NoOutputFormats);
+        } else if self.output.write_changes && !self.output.formats.contains(&OutputFormat::Json) {
+            errors.push(ConfigError::ChangesWithoutJson);
+        }
+        validate_output_keys(&self.output.keys, &mut errors);
+        for destination in &self.output.destinations {
+            if destination.path.trim().is_empty() {
+                errors.push(ConfigError::EmptyOutputPath(
+                    "destinations.path".to_string(),
+                ));
+            }
+            if let Some(keys) = &destination.keys {
+                validate_output_keys(keys, &mut errors);
+            }
+        }
+        if self.output.envelope
+            && ["", ENVELOPE_GENERATED_AT_KEY, ENVELOPE_VERSION_KEY]
+                .contains(&self.output.envelope_key.trim())
+        {
+            errors.push(ConfigError::InvalidEnvelopeKey(
+                self.output.envelope_key.clone(),
+            ));
+        }
+
+        if self.groups.is_empty() {
+            errors.push(ConfigError::NoGroups);
+        } else if !self.generation_label_matches_group() {
+            errors.push(ConfigError::GenerationLabelMatchesNoGroup(
+                self.generation.label.clone(),
+            ));
+        }
+        validate_groups(&self.groups, &mut errors);
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+impl Config {
+    /// Returns whether any of the generation labels is the label of a group, including the child groups,
+    /// ignoring case if `generation.case_insensitive_labels` is enabled.
+    fn generation_label_matches_group(&self) -> bool {
+        fn matches(groups: &[GroupConfig], label: &str, ignore_case: bool) -> bool {
+            groups.iter().any(|group| {
+                group.label.iter().any(|group_label| {
+                    if ignore_case {
+                        group_label.to_lowercase() == label.to_lowercase()
+                    } else {
+                        group_label == label
+                    }
+                }) || matches(&group.children, label, ignore_case)
+            })
+        }
+
+        self.generation
+            .label
+            .iter()
+            .any(|label| matches(&self.groups, label, self.generation.case_insensitive_labels))
+    }
+
+    /// Returns the field identifying the entries in the changes report, `generation.identity_field`
+    /// if it is set or `output.changes_key` otherwise, or `None` if the entries are identified by their issue ID.
+    pub fn changes_identity_field(&self) -> Option<&str> {
+        self.generation
+            .identity_field
+            .as_deref()
+            .or(self.output.changes_key.as_deref())
+    }
+
+    /// Returns all the labels used by the configuration: the generation labels, the excluded labels,
+    /// the draft label, the labels of the label flags, and the labels of all the groups (including the child groups).
+    pub fn configured_labels(&self) -> Vec<&str> {
+        fn add_group_labels<'a>(groups: &'a [GroupConfig], labels: &mut Vec<&'a str>) {
+            for group in groups {
+                labels.extend(group.label.iter().map(String::as_str));
+                add_group_labels(&group.children, labels);
+            }
+        }
+
+        let mut labels: Vec<&str> = Vec::new();
+        labels.extend(self.generation.label.iter().map(String::as_str));
+        labels.extend(self.generation.exclude_labels.iter().map(String::as_str));
+        labels.extend(self.generation.draft_label.as_deref());
+        labels.extend(self.output.label_flags.keys().map(String::as_str));
+        add_group_labels(&self.groups, &mut labels);
+        labels
+    }
+}
+
+impl GenerationConfig {
+    /// Returns the field identifying the entries for the duplicate detection,
+    /// `identity_field` if it is set or `duplicate_key` otherwise.
+    pub fn duplicate_identity_field(&self) -> &str {
+        self.identity_field
+            .as_deref()
+            .unwrap_or(&self.duplicate_key)
+    }
+}
+
+impl GroupConfig {
+    /// Returns the first label of the group, which identifies the group in the output.
+    pub fn primary_label(&self) -> &str {
+        self.label.first().map(String::as_str).unwrap_or_default()
+    }
+
+    /// Returns whether an issue belongs to this group, i.e. carries any of its labels,
+    /// where `has_label` tells whether the issue carries a given label.
+    pub fn matches(&self, has_label: impl Fn(&str) -> bool) -> bool {
+        LabelMatch::Any.matches(&self.label, has_label)
+    }
+}
+
+impl GroupSort {
+    /// Returns the sort order of a child group, each field not set falling back to the field of the resolved
+    /// sort order of its parent group, if any (and then to the generation configuration, see `GroupSort`).
+    pub fn inherit(&self, parent: Option<&GroupSort>) -> GroupSort {
+        GroupSort {
+            sort_by: self.sort_by.or(parent.and_then(|parent| parent.sort_by)),
+            sort_field: self
+                .sort_field
+                .clone()
+                .or_else(|| parent.and_then(|parent| parent.sort_field.clone())),
+            sort_by_updated_time: self
+                .sort_by_updated_time
+                .or(parent.and_then(|parent| parent.sort_by_updated_time)),
+            descending: self
+                .descending
+                .or(parent.and_then(|parent| parent.descending)),
+        }
+    }
+}
+
+/// Checks the key names of the group objects for empty and duplicate keys.
+fn validate_output_keys(keys: &OutputKeys, errors: &mut Vec<ConfigError>) {
+    let mut output_keys: HashSet<&str> = HashSet::new();
+    for (field, key) in keys.all() {
+        if key.trim().is_empty() {
+            errors.push(ConfigError::EmptyOutputKey(field.to_string()));
+        } else if !output_keys.insert(key) {
+            errors.push(ConfigError::DuplicateOutputKey(key.to_string()));
+        }
+    }
+}
+
+/// Checks a list of sibling groups, and recursively their child groups, for empty and duplicate labels.
+fn validate_groups(groups: &[GroupConfig], errors: &mut Vec<ConfigError>) {
+    let mut seen_labels: HashSet<&str> = HashSet::new();
+    let mut duplicate_labels: Vec<&str> = Vec::new();
+    for group in groups {
+        if group.label.is_empty() || group.label.iter().any(|label| label.trim().is_empty()) {
+            errors.push(ConfigError::EmptyGroupLabel(
+                group.name.clone().unwrap_or_default(),
+            ));
+        }
+        for label in &group.label {
+            if !label.trim().is_empty()
+                && !seen_labels.insert(label)
+                && !duplicate_labels.contains(&label.as_str())
+            {
+                duplicate_labels.push(label);
+                errors.push(ConfigError::DuplicateGroupLabel(label.clone()));
+            }
+        }
+        validate_groups(&group.children, errors);
+    }
 }
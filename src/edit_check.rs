@@ -0,0 +1,187 @@
+// This file contains the detection of the entries whose issue was edited after it was approved,
+// i.e. after the generation label was added to it.
+
+use crate::config::Config;
+use crate::github_api::{self, FetchError};
+use crate::github_api_responses::TimelineEvent;
+use crate::github_auth;
+use crate::link_entry::LinkEntry;
+use crate::logging::{log_info, log_warn};
+use chrono::{DateTime, SecondsFormat, Utc};
+use reqwest::header::{ACCEPT, AUTHORIZATION, USER_AGENT};
+use serde::Serialize;
+
+/// The number of timeline events requested per page from the GitHub API (the maximum allowed by the API).
+const EVENTS_PER_PAGE: usize = 100;
+
+/// The delay after the approval within which an update of the issue is not considered an edit,
+/// since adding the label itself updates the issue.
+const EDIT_TOLERANCE: chrono::Duration = chrono::Duration::seconds(5);
+
+/// An active entry whose issue was updated after it was approved.
+#[derive(Serialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct EditedEntry {
+    /// The ID of the entry.
+    pub id: usize,
+    /// The number of the issue of the entry.
+    pub number: usize,
+    /// The URL of the issue on GitHub, if known.
+    pub html_url: Option<String>,
+    /// The time when the generation label was last added to the issue, as an RFC 3339 string.
+    pub approved_at: String,
+    /// The time when the issue was last updated, as an RFC 3339 string.
+    pub updated_at: String,
+}
+
+/// This function finds the entries whose issue was updated after the generation label was last added to it,
+/// e.g. to point the entry at another site once it is approved, so that they can be reviewed again.
+///
+/// The time of the label is read from the timeline of the issue, which is only fetched for the issues
+/// updated after their creation. Note that the GitHub API does not tell what was updated, so an issue
+/// that was only commented on after its approval is also flagged.
+/// An issue whose timeline cannot be fetched is not flagged, with a warning printed.
+///
+/// ## Arguments
+/// - `config`: A reference to the `Config` struct that contains the GitHub options and the generation labels.
+/// - `entries`: The active entries, whose static entries (without an issue) are skipped.
+///
+/// ## Returns
+/// The entries edited after their approval, in the order of the entries.
+pub(crate) async fn check_edits(config: &Config, entries: &[LinkEntry]) -> Vec<EditedEntry> {
+    let client = match github_api::github_client(&config.network) {
+        Ok(client) => client,
+        Err(e) => {
+            log_warn!(
+                "Warning: Failed to Build HTTP Client, Skipping Edit Check: {}",
+                e
+            );
+            return Vec::new();
+        }
+    };
+    let github_token = match github_auth::github_token(config, &client).await {
+        Ok(token) => token,
+        Err(e) => {
+            log_warn!(
+                "Warning: Failed to Get GitHub Token, Skipping Edit Check: {}",
+                e
+            );
+            return Vec::new();
+        }
+    };
+
+    let candidates: Vec<(&LinkEntry, usize)> = entries
+        .iter()
+        .filter(|entry| entry.updated_at > entry.created_at + EDIT_TOLERANCE)
+        .filter_map(|entry| Some((entry, entry.number?)))
+        .collect();
+    log_info!(
+        "Checking the edits after approval of {} updated issues.",
+        candidates.len()
+    );
+
+    let mut edited: Vec<EditedEntry> = Vec::new();
+    for (entry, number) in candidates {
+        let events = match get_timeline(config, &client, &github_token, number).await {
+            Ok(events) => events,
+            Err(e) => {
+                log_warn!(
+                    "Warning: Failed to Fetch Timeline of Issue #{}, Skipping Edit Check: {}",
+                    number,
+                    e
+                );
+                continue;
+            }
+        };
+        let Some(approved_at) = approved_at(config, &events) else {
+            continue;
+        };
+        if entry.updated_at > approved_at + EDIT_TOLERANCE {
+            edited.push(EditedEntry {
+                id: entry.id,
+                number,
+                html_url: entry.html_url.clone(),
+                approved_at: approved_at.to_rfc3339_opts(SecondsFormat::Secs, true),
+                updated_at: entry.updated_at.to_rfc3339_opts(SecondsFormat::Secs, true),
+            });
+        }
+    }
+    edited
+}
+
+/// Returns the time when one of the generation labels was last added to the issue, according to its timeline,
+/// matching the labels ignoring case if `generation.case_insensitive_labels` is enabled.
+fn approved_at(config: &Config, events: &[TimelineEvent]) -> Option<DateTime<Utc>> {
+    let generation = &config.generation;
+    events
+        .iter()
+        .filter(|event| event.event.as_deref() == Some("labeled"))
+        .filter(|event| {
+            event.label.as_ref().is_some_and(|label| {
+                generation.label.iter().any(|configured| {
+                    if generation.case_insensitive_labels {
+                        configured.to_lowercase() == label.name.to_lowercase()
+                    } else {
+                        *configured == label.name
+                    }
+                })
+            })
+        })
+        .filter_map(|event| event.created_at.as_deref())
+        .filter_map(|time| DateTime::parse_from_rfc3339(time).ok())
+        .map(|time| time.with_timezone(&Utc))
+        .max()
+}
+
+/// This function retrieves all the events of the timeline of an issue, page by page.
+///
+/// See: https://docs.github.com/en/rest/issues/timeline?apiVersion=2022-11-28#list-timeline-events-for-an-issue
+///
+/// ## Arguments
+/// - `config`: A reference to a `Config` struct that contains the GitHub API URL, owner, repository name,
+///   and network options.
+/// - `client`: The Reqwest client used to send the requests.
+/// - `github_token`: The GitHub token used to authenticate the requests.
+/// - `number`: The number of the issue.
+///
+/// ## Returns
+/// The events of the timeline, or a `FetchError` if they could not be fetched.
+async fn get_timeline(
+    config: &Config,
+    client: &reqwest::Client,
+    github_token: &str,
+    number: usize,
+) -> Result<Vec<TimelineEvent>, FetchError> {
+    let url = format!(
+        "{}/repos/{}/{}/issues/{}/timeline",
+        config.github.api_url.trim_end_matches('/'),
+        config.github.owner,
+        config.github.repository,
+        number
+    );
+
+    let mut events: Vec<TimelineEvent> = Vec::new();
+    let mut page = 1;
+    loop {
+        let request = client
+            .get(&url)
+            .query(&[("per_page", EVENTS_PER_PAGE), ("page", page)])
+            .header(USER_AGENT, config.network.user_agent())
+            .header(ACCEPT, "application/vnd.github+json")
+            .header(AUTHORIZATION, format!("Bearer {}", github_token))
+            .header("X-GitHub-Api-Version", "2022-11-28");
+        let res = github_api::send_with_retry(&config.network, request).await?;
+        let body = res.text().await.map_err(FetchError::Request)?;
+        let page_events: Vec<TimelineEvent> = github_api::parse_list(&body)?;
+
+        // A page that is not full means that there are no more events to fetch.
+        let page_len = page_events.len();
+        events.extend(page_events);
+        if page_len < EVENTS_PER_PAGE {
+            break;
+        }
+        page += 1;
+    }
+
+    Ok(events)
+}
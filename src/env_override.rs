@@ -0,0 +1,134 @@
+// This file contains the overriding of the configuration fields with environment variables.
+
+use crate::logging::log_warn;
+use crate::profiles::PROFILE_VAR;
+
+/// The prefix of the environment variables overriding the configuration fields.
+pub(crate) const ENV_PREFIX: &str = "BFLDG_";
+/// The separator between the nested keys in the name of an environment variable.
+const KEY_SEPARATOR: &str = "__";
+
+/// This function overrides the fields of the parsed configuration file with the environment variables
+/// starting with `BFLDG_`, so that they win over the file.
+///
+/// The rest of the name of a variable is the path of the field, lowercased, with `__` between the nested keys,
+/// e.g. `BFLDG_GITHUB__OWNER` overrides `github.owner`, and `BFLDG_OUTPUT__KEYS__GROUP` overrides `output.keys.group`.
+/// The value is parsed as a TOML value (e.g. `true`, `30` or `["friend", "bestie"]`),
+/// and is used as a plain string if it is not a valid one (e.g. `active`).
+/// The missing tables are created, and the variables are applied in the order of their names.
+/// The `BFLDG_PROFILE` variable selects the profile instead (see `profiles::apply_profile`).
+///
+/// ## Arguments
+/// - `config`: The parsed configuration file, as a TOML table.
+/// - `vars`: The environment variables, as name and value pairs.
+///
+/// ## Returns
+/// The paths of the overridden fields, with the names of the environment variables, for logging.
+pub(crate) fn apply_env_overrides(
+    config: &mut toml::Table,
+    vars: impl IntoIterator<Item = (String, String)>,
+) -> Vec<(String, String)> {
+    let mut overrides: Vec<(String, String)> = vars
+        .into_iter()
+        .filter(|(name, _)| name.starts_with(ENV_PREFIX) && name.len() > ENV_PREFIX.len())
+        .filter(|(name, _)| name != PROFILE_VAR)
+        .collect();
+    overrides.sort();
+
+    let mut applied: Vec<(String, String)> = Vec::new();
+    for (name, raw_value) in overrides {
+        let path: Vec<String> = name[ENV_PREFIX.len()..]
+            .split(KEY_SEPARATOR)
+            .map(str::to_lowercase)
+            .collect();
+        if path.iter().any(String::is_empty) {
+            log_warn!("Ignoring Invalid Configuration Override: {}", name);
+            continue;
+        }
+
+        // Find the table of the field, creating (or replacing) the intermediate tables.
+        let (key, parents) = path.split_last().expect("The path is never empty");
+        let mut table = &mut *config;
+        for parent in parents {
+            let value = table
+                .entry(parent.clone())
+                .or_insert_with(|| toml::Value::Table(toml::Table::new()));
+            if !value.is_table() {
+                *value = toml::Value::Table(toml::Table::new());
+            }
+            table = value
+                .as_table_mut()
+                .expect("The value was just made a table");
+        }
+        table.insert(key.clone(), parse_value(&raw_value));
+        applied.push((path.join("."), name));
+    }
+    applied
+}
+
+/// Parses the value of an environment variable as a TOML value, or as a plain string if it is not a valid one.
+fn parse_value(raw_value: &str) -> toml::Value {
+    format!("value = {}", raw_value)
+        .parse::<toml::Table>()
+        .ok()
+        .and_then(|mut table| table.remove("value"))
+        .unwrap_or_else(|| toml::Value::String(raw_value.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vars(vars: &[(&str, &str)]) -> Vec<(String, String)> {
+        vars.iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn overrides_the_nested_fields_with_typed_values() {
+        let mut config: toml::Table = toml::from_str(
+            "[github]\nowner = \"o\"\nrepository = \"r\"\n[generation]\nlabel = \"active\"",
+        )
+        .unwrap();
+        let applied = apply_env_overrides(
+            &mut config,
+            vars(&[
+                ("BFLDG_GITHUB__OWNER", "other"),
+                ("BFLDG_GENERATION__LABEL", "[\"approved\", \"active\"]"),
+                ("BFLDG_GENERATION__STRICT", "true"),
+                ("BFLDG_OUTPUT__KEYS__GROUP", "category"),
+                ("OTHER_VAR", "ignored"),
+                ("BFLDG_PROFILE", "staging"),
+            ]),
+        );
+        let expected: toml::Table = toml::from_str(
+            "[github]\nowner = \"other\"\nrepository = \"r\"\n\
+             [generation]\nlabel = [\"approved\", \"active\"]\nstrict = true\n\
+             [output.keys]\ngroup = \"category\"",
+        )
+        .unwrap();
+        assert_eq!(config, expected);
+        // The overrides are applied in the order of their names.
+        assert_eq!(
+            applied,
+            vars(&[
+                ("generation.label", "BFLDG_GENERATION__LABEL"),
+                ("generation.strict", "BFLDG_GENERATION__STRICT"),
+                ("github.owner", "BFLDG_GITHUB__OWNER"),
+                ("output.keys.group", "BFLDG_OUTPUT__KEYS__GROUP"),
+            ])
+        );
+    }
+
+    #[test]
+    fn ignores_the_invalid_paths() {
+        let mut config = toml::Table::new();
+        let applied = apply_env_overrides(
+            &mut config,
+            vars(&[("BFLDG_", "x"), ("BFLDG_GITHUB____OWNER", "x")]),
+        );
+        assert!(applied.is_empty());
+        assert!(config.is_empty());
+    }
+}
@@ -0,0 +1,754 @@
+// This file contains the logic used to extract the friend link entry data from an issue body.
+
+use regex::Regex;
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// The comment that marks the start of the data section in the issue body.
+const DATA_START: &str = "<!-- DATA_START -->";
+/// The comment that marks the end of the data section in the issue body.
+const DATA_END: &str = "<!-- DATA_END -->";
+/// The opening fence of the JSON code block in the data section.
+const CODE_BLOCK_START: &str = "```json";
+/// The closing fence of the JSON code block in the data section.
+const CODE_BLOCK_END: &str = "```";
+/// The opening tag of an HTML `<details>` element, without its closing `>` since it can have attributes.
+const DETAILS_START: &str = "<details";
+/// The closing tag of an HTML `<details>` element.
+const DETAILS_END: &str = "</details>";
+/// The opening tag of an HTML `<summary>` element, without its closing `>` since it can have attributes.
+const SUMMARY_START: &str = "<summary";
+/// The closing tag of an HTML `<summary>` element.
+const SUMMARY_END: &str = "</summary>";
+/// The prefix of the heading of a field in the body of an issue created with a GitHub Issue Form.
+const FORM_HEADING: &str = "### ";
+/// The value rendered by GitHub Issue Forms for an optional field left empty.
+const FORM_NO_RESPONSE: &str = "_No response_";
+
+/// The options of the extraction of the entry data.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct ExtractOptions {
+    /// Whether to accept comments and trailing commas in the JSON code block.
+    pub(crate) lenient_json: bool,
+    /// Whether to accept other content (including other code blocks) in the data section,
+    /// using the first JSON code block containing valid data.
+    pub(crate) allow_other_content: bool,
+}
+
+/// The entry data extracted from an issue body.
+#[derive(Debug)]
+pub(crate) struct ExtractedEntry {
+    /// The parsed JSON data of the entry.
+    pub(crate) json_data: serde_json::Value,
+    /// Whether comments or trailing commas had to be removed from the code block to parse it.
+    pub(crate) lenient_fixed: bool,
+}
+
+/// The reasons why the entry data could not be extracted from an issue body.
+#[derive(Debug)]
+pub(crate) enum ExtractError {
+    /// The issue body is missing or empty.
+    EmptyBody,
+    /// The issue body is longer than the configured limit, in bytes.
+    BodyTooLarge { length: usize, max_length: usize },
+    /// The `<!-- DATA_START -->` or `<!-- DATA_END -->` comment is missing.
+    MissingMarkers,
+    /// The `<!-- DATA_START -->` comment comes after the `<!-- DATA_END -->` comment.
+    MarkersOutOfOrder,
+    /// More than one `<!-- DATA_START -->` or `<!-- DATA_END -->` comment exists.
+    MultipleMarkers,
+    /// The data section contains Markdown content other than the code block.
+    OtherContent,
+    /// The data section contains more than one code block.
+    MultipleCodeBlocks,
+    /// The data section does not contain any JSON code block.
+    NoCodeBlock,
+    /// The content of the code block is not valid JSON.
+    InvalidJson(serde_json::Error),
+    /// The content of the code block is valid JSON, but not an object (e.g. an array or a number),
+    /// with the kind of the value.
+    NotAnObject(&'static str),
+    /// None of the configured fields of the issue form has a value in the issue body.
+    NoFormFields,
+    /// The JSON file attached to the issue could not be downloaded, with the reason.
+    AttachmentFailed(String),
+}
+
+impl ExtractError {
+    /// Returns a short and stable code of the error, used for reporting.
+    pub(crate) fn code(&self) -> &'static str {
+        match self {
+            ExtractError::EmptyBody => "empty_body",
+            ExtractError::BodyTooLarge { .. } => "body_too_large",
+            ExtractError::MissingMarkers => "missing_markers",
+            ExtractError::MarkersOutOfOrder => "markers_out_of_order",
+            ExtractError::MultipleMarkers => "multiple_markers",
+            ExtractError::OtherContent => "other_content",
+            ExtractError::MultipleCodeBlocks => "multiple_code_blocks",
+            ExtractError::NoCodeBlock => "no_code_block",
+            ExtractError::InvalidJson(_) => "invalid_json",
+            ExtractError::NotAnObject(_) => "not_an_object",
+            ExtractError::NoFormFields => "no_form_fields",
+            ExtractError::AttachmentFailed(_) => "attachment_failed",
+        }
+    }
+}
+
+impl fmt::Display for ExtractError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExtractError::EmptyBody => write!(f, "The issue body is empty."),
+            ExtractError::BodyTooLarge { length, max_length } => write!(
+                f,
+                "The issue body is too large ({} bytes, at most {} allowed).",
+                length, max_length
+            ),
+            ExtractError::MissingMarkers => write!(f, "Missing DATA_START or DATA_END comment."),
+            ExtractError::MarkersOutOfOrder => {
+                write!(f, "DATA_START comment is after DATA_END comment.")
+            }
+            ExtractError::MultipleMarkers => {
+                write!(f, "Multiple DATA_START or DATA_END comments found.")
+            }
+            ExtractError::OtherContent => {
+                write!(f, "Other Markdown content found in the data section.")
+            }
+            ExtractError::MultipleCodeBlocks => write!(
+                f,
+                "Multiple code blocks (or other Markdown content) found in the data section."
+            ),
+            ExtractError::NoCodeBlock => write!(f, "No JSON code block found in the data section."),
+            ExtractError::InvalidJson(e) => write!(f, "Invalid JSON in the code block: {}", e),
+            ExtractError::NotAnObject(kind) => write!(
+                f,
+                "The JSON data in the code block must be an object, found {}.",
+                kind
+            ),
+            ExtractError::NoFormFields => {
+                write!(f, "None of the configured form fields has a value.")
+            }
+            ExtractError::AttachmentFailed(reason) => {
+                write!(f, "Failed to download the attached JSON file: {}.", reason)
+            }
+        }
+    }
+}
+
+/// This function extracts the JSON data of a friend link entry from an issue body,
+/// following the criteria described in the design documentation.
+///
+/// ## Criteria
+/// Based on the design documentation, the following standard must be met for an issue body to be valid:
+///
+/// 2. The issue body *can* be written in Markdown and *can* contain anything that can be written in Markdown.
+/// 3. The issue body *must* contain a **fenced code block**, for which:
+///     1. *contains* the data for the corresponding friend link entry.
+///     2. *must* be set to `json` language.
+///     3. *must* contain a valid JSON object containing data for the friend link entry.
+///     4. *should* be the **only** code block in the issue body.
+///     5. *must* be preceded by a `<!-- DATA_START -->` comment.
+///     6. *must* be followed by a `<!-- DATA_END -->` comment.
+///     7. *must* be the only Markdown content between the `<!-- DATA_START -->` and `<!-- DATA_END -->` comments.
+///     8. No other `<!-- DATA_START -->` or `<!-- DATA_END -->` comments can exist in the issue body.
+///
+/// *(some other parts are not included since they are not relevant to this function)*
+///
+/// Occurrences of the comments inside fenced code blocks (e.g. in a JSON string value) are ignored.
+///
+/// A single HTML `<details>` element wrapping the whole data section, with an optional `<summary>`,
+/// is not considered as other Markdown content, so that the code block can be collapsed in the issue.
+///
+/// When `options.allow_other_content` is set, rule 7 is relaxed: the data section can contain
+/// other Markdown content and code blocks, and the first JSON code block containing valid data is used.
+///
+/// When `options.lenient_json` is set, comments and trailing commas are removed from the code block
+/// before it is parsed, and the entry data is then normalized to strict JSON.
+///
+/// ## Arguments
+/// - `body`: The body of the issue.
+/// - `options`: The options of the extraction.
+///
+/// ## Returns
+/// The extracted data of the entry, or an `ExtractError` describing why the body is invalid.
+pub(crate) fn extract_entry_json(
+    body: &str,
+    options: &ExtractOptions,
+) -> Result<ExtractedEntry, ExtractError> {
+    // Find the indices of data start and end comments outside of code blocks, and check if they exist.
+    let data_start_indices = find_outside_code_blocks(body, DATA_START);
+    let data_end_indices = find_outside_code_blocks(body, DATA_END);
+    let (data_start_index, data_end_index) =
+        match (data_start_indices.first(), data_end_indices.first()) {
+            (Some(&start), Some(&end)) => (start, end),
+            _ => return Err(ExtractError::MissingMarkers),
+        };
+
+    // Check if the comments are in the correct order.
+    if data_start_index > data_end_index {
+        return Err(ExtractError::MarkersOutOfOrder);
+    }
+    // Check if the comments are the only pair in the body.
+    if data_start_indices.len() != 1 || data_end_indices.len() != 1 {
+        return Err(ExtractError::MultipleMarkers);
+    }
+
+    // Extract the data section between the comments.
+    let data_section =
+        strip_details_wrapper(body[data_start_index + DATA_START.len()..data_end_index].trim());
+
+    // Use the first JSON code block containing valid data if other content is allowed.
+    if options.allow_other_content {
+        let mut first_error: Option<ExtractError> = None;
+        for code_block in json_code_blocks(data_section) {
+            match parse_code_block(code_block, options) {
+                Ok(extracted) => return Ok(extracted),
+                Err(e) => {
+                    first_error.get_or_insert(e);
+                }
+            }
+        }
+        return Err(first_error.unwrap_or(ExtractError::NoCodeBlock));
+    }
+
+    // Check if only a code block exists in the data section.
+    if !(data_section.starts_with(CODE_BLOCK_START) && data_section.ends_with(CODE_BLOCK_END)) {
+        return Err(ExtractError::OtherContent);
+    }
+    // Check if the code block is the only one in the data section.
+    // The check is `data_section.matches(CODE_BLOCK_END).count() != 2` is done as the bit "```" is also included in the start of the code block.
+    if data_section.matches(CODE_BLOCK_START).count() != 1
+        || data_section.matches(CODE_BLOCK_END).count() != 2
+    {
+        return Err(ExtractError::MultipleCodeBlocks);
+    }
+
+    // Extract the code block content.
+    let code_block =
+        &data_section[CODE_BLOCK_START.len()..data_section.len() - CODE_BLOCK_END.len()];
+
+    parse_code_block(code_block, options)
+}
+
+/// This function removes a single HTML `<details>` element wrapping the whole data section,
+/// and the `<summary>` element at its start, if any, e.g.
+/// `<details><summary>Data</summary> ```json ... ``` </details>`.
+///
+/// ## Arguments
+/// - `data_section`: The trimmed data section, between the markers.
+///
+/// ## Returns
+/// The trimmed content of the `<details>` element, or the data section as is if it is not wrapped in one.
+fn strip_details_wrapper(data_section: &str) -> &str {
+    if !(data_section.starts_with(DETAILS_START) && data_section.ends_with(DETAILS_END)) {
+        return data_section;
+    }
+    // The opening tag must be a `<details>` tag, and not e.g. a `<detailsfoo>` one.
+    let Some(tag_end) = data_section.find('>') else {
+        return data_section;
+    };
+    let is_details_tag = data_section[DETAILS_START.len()..tag_end]
+        .chars()
+        .next()
+        .map_or(true, char::is_whitespace);
+    if !is_details_tag || tag_end + 1 > data_section.len() - DETAILS_END.len() {
+        return data_section;
+    }
+    let content = data_section[tag_end + 1..data_section.len() - DETAILS_END.len()].trim();
+
+    // Remove the summary of the details element, if any.
+    if content.starts_with(SUMMARY_START) {
+        if let Some(summary_end) = content.find(SUMMARY_END) {
+            return content[summary_end + SUMMARY_END.len()..].trim();
+        }
+    }
+    content
+}
+
+/// This function extracts the data of a friend link entry from the body of an issue created with
+/// a GitHub Issue Form, which is rendered as a `### Heading` line followed by the value of the field
+/// for every field of the form.
+///
+/// The value of every field whose heading is mapped in `form_fields` is added, as a trimmed string,
+/// to the entry data under the mapped name. The other headings are ignored, as well as the fields
+/// left empty (rendered as `_No response_`), and the headings inside fenced code blocks.
+///
+/// ## Arguments
+/// - `body`: The body of the issue.
+/// - `form_fields`: The headings of the form fields, mapped to the names of the entry fields.
+///
+/// ## Returns
+/// The extracted data of the entry, or `ExtractError::NoFormFields` if none of the mapped fields has a value.
+pub(crate) fn extract_form_entry(
+    body: &str,
+    form_fields: &BTreeMap<String, String>,
+) -> Result<ExtractedEntry, ExtractError> {
+    // Split the body into the sections of the fields, as heading and value pairs.
+    let mut sections: Vec<(&str, String)> = Vec::new();
+    let mut in_code_block = false;
+    for line in body.lines() {
+        if line.trim_start().starts_with(CODE_BLOCK_END) {
+            in_code_block = !in_code_block;
+        } else if !in_code_block {
+            if let Some(heading) = line.strip_prefix(FORM_HEADING) {
+                sections.push((heading.trim(), String::new()));
+                continue;
+            }
+        }
+        if let Some((_, value)) = sections.last_mut() {
+            value.push_str(line);
+            value.push('\n');
+        }
+    }
+
+    let mut object = serde_json::Map::new();
+    for (heading, value) in sections {
+        let value = value.trim();
+        if value.is_empty() || value == FORM_NO_RESPONSE {
+            continue;
+        }
+        if let Some(field) = form_fields.get(heading) {
+            object.insert(field.clone(), serde_json::Value::String(value.to_string()));
+        }
+    }
+    if object.is_empty() {
+        return Err(ExtractError::NoFormFields);
+    }
+
+    Ok(ExtractedEntry {
+        json_data: serde_json::Value::Object(object),
+        lenient_fixed: false,
+    })
+}
+
+/// This function returns whether the issue body contains a `<!-- DATA_START -->` or `<!-- DATA_END -->` comment
+/// outside of the code blocks, in which case the inline code block is used rather than an attachment.
+pub(crate) fn has_data_markers(body: &str) -> bool {
+    !find_outside_code_blocks(body, DATA_START).is_empty()
+        || !find_outside_code_blocks(body, DATA_END).is_empty()
+}
+
+/// This function finds the first JSON file attached to an issue body, as a Markdown link or image
+/// (e.g. `[links.json](https://github.com/user-attachments/files/123/links.json)`).
+///
+/// Only the attachments uploaded to GitHub are accepted: the URL must use HTTPS on the `github.com` host,
+/// be under `/user-attachments/files/` or the `/files/` of a repository, and end with a `.json` file name,
+/// so that the script never downloads arbitrary URLs. The links inside code blocks are ignored.
+///
+/// ## Arguments
+/// - `body`: The body of the issue.
+///
+/// ## Returns
+/// The URL of the attachment, if any.
+pub(crate) fn find_json_attachment(body: &str) -> Option<&str> {
+    let attachment = Regex::new(
+        r"^https://github\.com/(?:user-attachments/files|[A-Za-z0-9_.-]+/[A-Za-z0-9_.-]+/files)/\d+/[A-Za-z0-9_.%-]+\.json$",
+    )
+    .unwrap();
+    find_outside_code_blocks(body, "](")
+        .into_iter()
+        .filter_map(|index| {
+            let target = &body[index + "](".len()..];
+            target
+                .find(|c: char| c == ')' || c.is_whitespace())
+                .map(|end| &target[..end])
+        })
+        .find(|url| attachment.is_match(url))
+}
+
+/// This function parses the content of a JSON file attached to an issue as the entry data,
+/// like the content of a code block.
+///
+/// ## Arguments
+/// - `content`: The content of the attached file.
+/// - `options`: The options of the extraction.
+///
+/// ## Returns
+/// The extracted data of the entry, or an `ExtractError` if it is not a valid JSON object.
+pub(crate) fn parse_attachment(
+    content: &str,
+    options: &ExtractOptions,
+) -> Result<ExtractedEntry, ExtractError> {
+    parse_code_block(content, options)
+}
+
+/// Parses the content of a code block as a JSON object, removing comments and trailing commas first if enabled.
+fn parse_code_block(
+    code_block: &str,
+    options: &ExtractOptions,
+) -> Result<ExtractedEntry, ExtractError> {
+    let extracted = if options.lenient_json {
+        let stripped = strip_trailing_commas(&strip_comments(code_block));
+        let json_data = serde_json::from_str(&stripped).map_err(ExtractError::InvalidJson)?;
+        ExtractedEntry {
+            json_data,
+            lenient_fixed: stripped != code_block,
+        }
+    } else {
+        let json_data = serde_json::from_str(code_block).map_err(ExtractError::InvalidJson)?;
+        ExtractedEntry {
+            json_data,
+            lenient_fixed: false,
+        }
+    };
+
+    // The rest of the pipeline expects the entry data to be an object.
+    let kind = match &extracted.json_data {
+        serde_json::Value::Object(_) => return Ok(extracted),
+        serde_json::Value::Array(_) => "an array",
+        serde_json::Value::String(_) => "a string",
+        serde_json::Value::Number(_) => "a number",
+        serde_json::Value::Bool(_) => "a boolean",
+        serde_json::Value::Null => "null",
+    };
+    Err(ExtractError::NotAnObject(kind))
+}
+
+/// Returns the indices of all the occurrences of a pattern in a Markdown text,
+/// ignoring the ones inside fenced code blocks.
+///
+/// The fence lines themselves are searched too, outside of their fence: e.g. the `<!-- DATA_END -->` comment
+/// of a `` ```<!-- DATA_END --> `` line closing a code block, or the `<!-- DATA_START -->` comment of a
+/// `` <!-- DATA_START -->```json `` line opening one, as accepted by the former versions of the script.
+fn find_outside_code_blocks(markdown: &str, pattern: &str) -> Vec<usize> {
+    let mut indices: Vec<usize> = Vec::new();
+    let mut in_code_block = false;
+    let mut line_start = 0;
+    for line in markdown.split_inclusive('\n') {
+        // The range of the line outside of the code blocks, if any.
+        let outside = if in_code_block {
+            // A line starting with the fence closes the code block, the rest of the line is outside of it.
+            let trimmed = line.trim_start();
+            trimmed.starts_with(CODE_BLOCK_END).then(|| {
+                in_code_block = false;
+                line.len() - trimmed.len() + CODE_BLOCK_END.len()..line.len()
+            })
+        } else {
+            match opening_fence_index(line) {
+                Some(fence_index) => {
+                    in_code_block = true;
+                    Some(0..fence_index)
+                }
+                None => Some(0..line.len()),
+            }
+        };
+        if let Some(outside) = outside {
+            let offset = line_start + outside.start;
+            indices.extend(
+                line[outside]
+                    .match_indices(pattern)
+                    .map(|(index, _)| offset + index),
+            );
+        }
+        line_start += line.len();
+    }
+    indices
+}
+
+/// Returns the index of the opening fence of a code block in a line, if the line opens one.
+///
+/// The fence must start the line, possibly after data comments (e.g. `` <!-- DATA_START -->```json ``),
+/// and only be followed by an info string (e.g. `json`) without any backtick or HTML comment, so that
+/// a line containing a whole code block (e.g. `` ```json {} ``` ``) does not open one.
+fn opening_fence_index(line: &str) -> Option<usize> {
+    let mut rest = line.trim_start();
+    while let Some(after_marker) = [DATA_START, DATA_END]
+        .iter()
+        .find_map(|marker| rest.strip_prefix(marker))
+    {
+        rest = after_marker.trim_start();
+    }
+    let info = rest.strip_prefix(CODE_BLOCK_END)?;
+    (!info.contains('`') && !info.contains("<!--")).then_some(line.len() - rest.len())
+}
+
+/// Returns the content of all the fenced code blocks set to `json` language in a Markdown text, in order.
+///
+/// A code block starts with a line containing only the opening fence (i.e. `` ```json ``),
+/// and ends with the next line starting with the closing fence (i.e. `` ``` ``).
+/// Code blocks in other languages are skipped.
+fn json_code_blocks(markdown: &str) -> Vec<&str> {
+    let mut blocks: Vec<&str> = Vec::new();
+    // The start index of the content of the current code block, and whether it is a JSON one.
+    let mut current_block: Option<(usize, bool)> = None;
+    let mut line_start = 0;
+    for line in markdown.split_inclusive('\n') {
+        let trimmed = line.trim();
+        match current_block {
+            None if trimmed.starts_with(CODE_BLOCK_END) => {
+                current_block = Some((line_start + line.len(), trimmed == CODE_BLOCK_START));
+            }
+            Some((content_start, is_json)) if trimmed.starts_with(CODE_BLOCK_END) => {
+                if is_json {
+                    blocks.push(&markdown[content_start..line_start]);
+                }
+                current_block = None;
+            }
+            _ => {}
+        }
+        line_start += line.len();
+    }
+    blocks
+}
+
+/// Removes the `//` line comments and `/* */` block comments outside of strings from a JSON text.
+fn strip_comments(json: &str) -> String {
+    let mut result = String::with_capacity(json.len());
+    let mut chars = json.chars().peekable();
+    let mut in_string = false;
+    while let Some(c) = chars.next() {
+        if in_string {
+            result.push(c);
+            if c == '\\' {
+                // Keep the escaped character, which may be a quote.
+                if let Some(escaped) = chars.next() {
+                    result.push(escaped);
+                }
+            } else if c == '"' {
+                in_string = false;
+            }
+            continue;
+        }
+        match (c, chars.peek()) {
+            ('"', _) => {
+                in_string = true;
+                result.push(c);
+            }
+            ('/', Some('/')) => {
+                // Skip until the end of the line, keeping the line break.
+                while chars.peek().is_some_and(|&next| next != '\n') {
+                    chars.next();
+                }
+            }
+            ('/', Some('*')) => {
+                // Skip until the end of the block comment.
+                chars.next();
+                let mut previous = '\0';
+                for next in chars.by_ref() {
+                    if previous == '*' && next == '/' {
+                        break;
+                    }
+                    previous = next;
+                }
+            }
+            _ => result.push(c),
+        }
+    }
+    result
+}
+
+/// Removes the commas outside of strings that are directly followed (ignoring whitespace)
+/// by the end of an object or an array from a JSON text.
+fn strip_trailing_commas(json: &str) -> String {
+    let chars: Vec<char> = json.chars().collect();
+    let mut result = String::with_capacity(json.len());
+    let mut in_string = false;
+    let mut index = 0;
+    while index < chars.len() {
+        let c = chars[index];
+        if in_string {
+            if c == '\\' && index + 1 < chars.len() {
+                result.push(c);
+                index += 1;
+                result.push(chars[index]);
+            } else {
+                if c == '"' {
+                    in_string = false;
+                }
+                result.push(c);
+            }
+        } else if c == '"' {
+            in_string = true;
+            result.push(c);
+        } else if c == ',' {
+            let next = chars[index + 1..].iter().find(|next| !next.is_whitespace());
+            if !matches!(next, Some('}') | Some(']')) {
+                result.push(c);
+            }
+        } else {
+            result.push(c);
+        }
+        index += 1;
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn extract(body: &str) -> Result<ExtractedEntry, ExtractError> {
+        extract_entry_json(body, &ExtractOptions::default())
+    }
+
+    #[test]
+    fn extracts_the_code_block_between_the_markers() {
+        let body =
+            "Hello!\n\n<!-- DATA_START -->\n```json\n{ \"name\": \"A\" }\n```\n<!-- DATA_END -->\n";
+        assert_eq!(extract(body).unwrap().json_data, json!({ "name": "A" }));
+    }
+
+    #[test]
+    fn finds_the_markers_on_the_fence_lines() {
+        // The compact layout accepted by the former versions of the script.
+        let body = "<!-- DATA_START -->```json\n{ \"name\": \"A\" }\n```<!-- DATA_END -->";
+        assert_eq!(extract(body).unwrap().json_data, json!({ "name": "A" }));
+        let body = "<!-- DATA_START -->\n```json\n{ \"name\": \"A\" }\n```<!-- DATA_END -->";
+        assert_eq!(extract(body).unwrap().json_data, json!({ "name": "A" }));
+        let body = "<!-- DATA_START -->```json\n{ \"name\": \"A\" }\n```\n<!-- DATA_END -->";
+        assert_eq!(extract(body).unwrap().json_data, json!({ "name": "A" }));
+        let body = "<!-- DATA_START -->```json { \"name\": \"A\" } ```<!-- DATA_END -->";
+        assert_eq!(extract(body).unwrap().json_data, json!({ "name": "A" }));
+    }
+
+    #[test]
+    fn ignores_the_markers_inside_code_blocks() {
+        let body = "<!-- DATA_START -->\n```json\n{ \"name\": \"<!-- DATA_START --> and <!-- DATA_END -->\" }\n```\n<!-- DATA_END -->";
+        assert_eq!(
+            extract(body).unwrap().json_data["name"],
+            "<!-- DATA_START --> and <!-- DATA_END -->"
+        );
+        let body = "Example:\n```\n<!-- DATA_START -->\n<!-- DATA_END -->\n```\n<!-- DATA_START -->\n```json\n{}\n```\n<!-- DATA_END -->";
+        assert_eq!(extract(body).unwrap().json_data, json!({}));
+        assert!(!has_data_markers("```\n<!-- DATA_START -->\n```"));
+        assert!(has_data_markers("```\ncode\n```<!-- DATA_END -->"));
+    }
+
+    #[test]
+    fn rejects_missing_duplicate_and_misordered_markers() {
+        assert!(matches!(
+            extract("```json\n{}\n```"),
+            Err(ExtractError::MissingMarkers)
+        ));
+        assert!(matches!(
+            extract("<!-- DATA_END -->\n```json\n{}\n```\n<!-- DATA_START -->"),
+            Err(ExtractError::MarkersOutOfOrder)
+        ));
+        let pair = "<!-- DATA_START -->\n```json\n{}\n```\n<!-- DATA_END -->";
+        assert!(matches!(
+            extract(&format!("{}\n{}", pair, pair)),
+            Err(ExtractError::MultipleMarkers)
+        ));
+    }
+
+    #[test]
+    fn rejects_the_values_other_than_an_object() {
+        for (json, kind) in [
+            ("[\"a\", \"b\"]", "an array"),
+            ("42", "a number"),
+            ("\"a\"", "a string"),
+            ("true", "a boolean"),
+            ("null", "null"),
+        ] {
+            let body = format!(
+                "<!-- DATA_START -->\n```json\n{}\n```\n<!-- DATA_END -->",
+                json
+            );
+            match extract(&body) {
+                Err(ExtractError::NotAnObject(found)) => assert_eq!(found, kind),
+                other => panic!("Unexpected result for {}: {:?}", json, other),
+            }
+        }
+    }
+
+    #[test]
+    fn sees_through_a_details_wrapper() {
+        let body = "<!-- DATA_START -->\n<details><summary>Data</summary>\n\n```json\n{ \"name\": \"A\" }\n```\n\n</details>\n<!-- DATA_END -->";
+        assert_eq!(extract(body).unwrap().json_data, json!({ "name": "A" }));
+        let body = "<!-- DATA_START --><details open>\n```json\n{ \"name\": \"A\" }\n```\n</details><!-- DATA_END -->";
+        assert_eq!(extract(body).unwrap().json_data, json!({ "name": "A" }));
+        // Only an element wrapping the whole data section is removed.
+        let body = "<!-- DATA_START -->\n<details><summary>Data</summary>\n```json\n{}\n```\n</details>\nMore text\n<!-- DATA_END -->";
+        assert!(matches!(extract(body), Err(ExtractError::OtherContent)));
+        let body =
+            "<!-- DATA_START -->\n<detailsfoo>\n```json\n{}\n```\n</details>\n<!-- DATA_END -->";
+        assert!(matches!(extract(body), Err(ExtractError::OtherContent)));
+    }
+
+    #[test]
+    fn reports_the_reason_of_every_invalid_body_shape() {
+        let cases = [
+            ("No data at all.", "missing_markers"),
+            ("<!-- DATA_START -->\n```json\n{}\n```", "missing_markers"),
+            (
+                "<!-- DATA_END --><!-- DATA_START -->",
+                "markers_out_of_order",
+            ),
+            (
+                "<!-- DATA_START --><!-- DATA_START -->\n```json\n{}\n```\n<!-- DATA_END -->",
+                "multiple_markers",
+            ),
+            ("<!-- DATA_START -->\n<!-- DATA_END -->", "other_content"),
+            (
+                "<!-- DATA_START -->\n```\n{}\n```\n<!-- DATA_END -->",
+                "other_content",
+            ),
+            (
+                "<!-- DATA_START -->\n```json\n{}\n```\n```json\n{}\n```\n<!-- DATA_END -->",
+                "multiple_code_blocks",
+            ),
+            (
+                "<!-- DATA_START -->\n```json\n{ \"name\": }\n```\n<!-- DATA_END -->",
+                "invalid_json",
+            ),
+            (
+                "<!-- DATA_START -->\n```json\n\n```\n<!-- DATA_END -->",
+                "invalid_json",
+            ),
+            (
+                "<!-- DATA_START -->\n```json\n[]\n```\n<!-- DATA_END -->",
+                "not_an_object",
+            ),
+        ];
+        for (body, code) in cases {
+            match extract(body) {
+                Err(e) => assert_eq!(e.code(), code, "Unexpected reason for {:?}: {}", body, e),
+                Ok(extracted) => panic!("Unexpected entry for {:?}: {}", body, extracted.json_data),
+            }
+        }
+    }
+
+    #[test]
+    fn only_accepts_a_single_code_block_by_default() {
+        assert!(matches!(
+            extract("<!-- DATA_START -->\nMy site:\n```json\n{}\n```\n<!-- DATA_END -->"),
+            Err(ExtractError::OtherContent)
+        ));
+        assert!(matches!(
+            extract("<!-- DATA_START -->\n```json\n{}\n```\n```json\n{}\n```\n<!-- DATA_END -->"),
+            Err(ExtractError::MultipleCodeBlocks)
+        ));
+
+        let options = ExtractOptions {
+            allow_other_content: true,
+            ..ExtractOptions::default()
+        };
+        let body = "<!-- DATA_START -->\nMy site:\n```toml\nname = \"B\"\n```\n```json\nnot json\n```\n```json\n{ \"name\": \"A\" }\n```\n<!-- DATA_END -->";
+        let extracted = extract_entry_json(body, &options).unwrap();
+        assert_eq!(extracted.json_data, json!({ "name": "A" }));
+        assert!(matches!(
+            extract_entry_json("<!-- DATA_START -->\ntext\n<!-- DATA_END -->", &options),
+            Err(ExtractError::NoCodeBlock)
+        ));
+    }
+
+    #[test]
+    fn accepts_comments_and_trailing_commas_in_lenient_mode() {
+        let body = "<!-- DATA_START -->\n```json\n{\n  // The name.\n  \"name\": \"A // B\", /* c */\n  \"tags\": [1, 2,],\n}\n```\n<!-- DATA_END -->";
+        assert!(matches!(extract(body), Err(ExtractError::InvalidJson(_))));
+
+        let options = ExtractOptions {
+            lenient_json: true,
+            ..ExtractOptions::default()
+        };
+        let extracted = extract_entry_json(body, &options).unwrap();
+        assert_eq!(
+            extracted.json_data,
+            json!({ "name": "A // B", "tags": [1, 2] })
+        );
+        assert!(extracted.lenient_fixed);
+
+        let body = "<!-- DATA_START -->\n```json\n{ \"name\": \"A\" }\n```\n<!-- DATA_END -->";
+        assert!(!extract_entry_json(body, &options).unwrap().lenient_fixed);
+    }
+}
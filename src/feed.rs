@@ -0,0 +1,93 @@
+// This file contains the rendering of the Atom feed of the newest friend link entries.
+
+use crate::config::Config;
+use crate::link_entry::LinkEntry;
+use crate::sanitize::escape_html;
+
+/// This function renders an Atom feed of the newest entries, so that the readers can subscribe to new friend links.
+///
+/// The entries are sorted by their creation time, from the newest to the oldest, and limited to `output.feed_limit`.
+/// Every item uses the `name` field of the entry as its title (falling back to `title` or `url`),
+/// the `url` field as its link, and the creation time of the issue as its publication time.
+///
+/// ## Arguments
+/// - `config`: A reference to the `Config` struct that contains the feed options.
+/// - `entries`: The entries included in the generated data.
+///
+/// ## Returns
+/// The Atom feed, as an XML string.
+pub(crate) fn render_feed(config: &Config, entries: &[&LinkEntry]) -> String {
+    let mut entries: Vec<&LinkEntry> = entries.to_vec();
+    entries.sort_by(|a, b| b.created_at.cmp(&a.created_at).then(b.id.cmp(&a.id)));
+    entries.truncate(config.output.feed_limit);
+
+    let feed_link = config.output.feed_link.clone().unwrap_or_else(|| {
+        format!(
+            "https://github.com/{}/{}",
+            config.github.owner, config.github.repository
+        )
+    });
+    // The feed is updated when its newest entry is added.
+    let updated = entries
+        .first()
+        .map_or_else(chrono::Utc::now, |entry| entry.created_at);
+
+    let mut xml = String::new();
+    xml.push_str("<?xml version=\"1.0\" encoding=\"utf-8\"?>\n");
+    xml.push_str("<feed xmlns=\"http://www.w3.org/2005/Atom\">\n");
+    xml.push_str(&format!(
+        "  <title>{}</title>\n",
+        escape_html(&config.output.feed_title)
+    ));
+    xml.push_str(&format!("  <link href=\"{}\"/>\n", escape_html(&feed_link)));
+    xml.push_str(&format!("  <id>{}</id>\n", escape_html(&feed_link)));
+    xml.push_str(&format!("  <updated>{}</updated>\n", updated.to_rfc3339()));
+    for entry in entries {
+        let url = string_field(entry, "url");
+        let title = string_field(entry, "name")
+            .or_else(|| string_field(entry, "title"))
+            .or(url)
+            .unwrap_or_default();
+        let id = url.map_or_else(|| format!("urn:friend-link:{}", entry.id), str::to_string);
+
+        xml.push_str("  <entry>\n");
+        xml.push_str(&format!("    <title>{}</title>\n", xml_text(title)));
+        if let Some(url) = url {
+            xml.push_str(&format!("    <link href=\"{}\"/>\n", escape_html(url)));
+        }
+        xml.push_str(&format!("    <id>{}</id>\n", escape_html(&id)));
+        xml.push_str(&format!(
+            "    <published>{}</published>\n",
+            entry.created_at.to_rfc3339()
+        ));
+        xml.push_str(&format!(
+            "    <updated>{}</updated>\n",
+            entry.created_at.to_rfc3339()
+        ));
+        if let Some(description) = string_field(entry, "description") {
+            xml.push_str(&format!(
+                "    <summary>{}</summary>\n",
+                xml_text(description)
+            ));
+        }
+        xml.push_str("  </entry>\n");
+    }
+    xml.push_str("</feed>\n");
+    xml
+}
+
+/// Returns the value of a field of the entry data if it is a non-empty string.
+fn string_field<'a>(entry: &'a LinkEntry, key: &str) -> Option<&'a str> {
+    entry.json_data[key]
+        .as_str()
+        .filter(|value| !value.trim().is_empty())
+}
+
+/// Escapes a string supplied by a contributor for the feed, removing the control characters that are not allowed in XML.
+fn xml_text(value: &str) -> String {
+    let allowed: String = value
+        .chars()
+        .filter(|c| !c.is_control() || matches!(c, '\t' | '\n' | '\r'))
+        .collect();
+    escape_html(&allowed)
+}
@@ -0,0 +1,95 @@
+// This file contains the progress of the fetch of the issues, persisted after every page
+// so that a run interrupted in the middle of the pagination can resume where it left off.
+
+use crate::github_api_responses::Issue;
+use crate::logging::{log_error, log_info, log_warn};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+/// The structure of the persisted progress of an interrupted fetch of the issues.
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct FetchProgress {
+    /// The URL of the issues endpoint of the repository.
+    pub(crate) url: String,
+    /// The `since` parameter of the fetch, if any.
+    pub(crate) since: Option<String>,
+    /// Whether the closed issues are also fetched.
+    pub(crate) include_closed: bool,
+    /// The time when the last page was fetched, as an RFC 3339 string.
+    pub(crate) saved_at: String,
+    /// The IDs of the issues of the first page, compared with a new first page to detect a changed list.
+    pub(crate) first_page_ids: Vec<usize>,
+    /// The number of pages fetched.
+    pub(crate) pages: usize,
+    /// The issues of the fetched pages.
+    pub(crate) issues: Vec<Issue>,
+}
+
+impl FetchProgress {
+    /// Loads the progress of an interrupted fetch from a file, returning `None` if the file does not exist,
+    /// cannot be parsed, is not for the same fetch (e.g. another repository or `since` parameter),
+    /// or is older than `window_seconds`.
+    pub(crate) fn load(
+        path: &Path,
+        url: &str,
+        since: Option<&str>,
+        include_closed: bool,
+        window_seconds: u64,
+    ) -> Option<FetchProgress> {
+        let content = fs::read_to_string(path).ok()?;
+        let progress: FetchProgress = match serde_json::from_str(&content) {
+            Ok(progress) => progress,
+            Err(e) => {
+                log_warn!(
+                    "Ignoring the invalid fetch progress {}: {}",
+                    path.display(),
+                    e
+                );
+                return None;
+            }
+        };
+        if progress.url != url
+            || progress.since.as_deref() != since
+            || progress.include_closed != include_closed
+        {
+            log_info!("Ignoring the fetch progress of another fetch of the issues.");
+            return None;
+        }
+        let age = DateTime::parse_from_rfc3339(&progress.saved_at)
+            .map(|saved_at| Utc::now().signed_duration_since(saved_at))
+            .ok()?;
+        if age.num_seconds() < 0 || age.num_seconds() as u64 > window_seconds {
+            log_info!(
+                "Ignoring the fetch progress saved {} seconds ago, the issues may have changed since then.",
+                age.num_seconds()
+            );
+            return None;
+        }
+        Some(progress)
+    }
+
+    /// Saves the progress to a file, creating its parent directory if needed.
+    /// A failure is only reported, since the next run can still fetch all the pages.
+    pub(crate) fn save(&self, path: &Path) {
+        let result = path
+            .parent()
+            .filter(|parent| !parent.as_os_str().is_empty())
+            .map_or(Ok(()), fs::create_dir_all)
+            .and_then(|_| fs::write(path, serde_json::to_string(self).unwrap()));
+        if let Err(e) = result {
+            log_error!("Failed to Save Fetch Progress {}: {}", path.display(), e);
+        }
+    }
+
+    /// Removes the progress file once the fetch is complete, if it exists.
+    pub(crate) fn clear(path: &Path) {
+        if let Err(e) = fs::remove_file(path) {
+            if e.kind() != std::io::ErrorKind::NotFound {
+                log_error!("Failed to Remove Fetch Progress {}: {}", path.display(), e);
+            }
+        }
+    }
+}
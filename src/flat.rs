@@ -0,0 +1,71 @@
+// This file contains the flat list of the entries, for the consumers that do not want the nested group objects.
+
+use crate::config::{OutputConfig, OutputKeys};
+use indexmap::IndexMap;
+use serde_json::Value;
+
+/// This function flattens the JSON data of the groups, including the child groups, into the list of their entries,
+/// adding the label of the group of every entry under the `flat_group_key` key, in the order of the groups.
+///
+/// An entry in several groups appears once per group, with the label of that group, or only once with
+/// the list of the labels of all its groups if `flat_group_array` is enabled, in which case the entries
+/// are identified by their data. A same-named field of the entry is replaced.
+///
+/// ## Arguments
+/// - `output`: A reference to the `OutputConfig` struct that contains the options of the flat file.
+/// - `groups`: The JSON data of the groups, with the key names of `output.keys`.
+///
+/// ## Returns
+/// The entries, each with its group label or labels.
+pub(crate) fn flatten_groups(output: &OutputConfig, groups: &[Value]) -> Vec<Value> {
+    let mut entries: Vec<(String, &Value)> = Vec::new();
+    collect_entries(groups, &output.keys, &mut entries);
+
+    if !output.flat_group_array {
+        return entries
+            .into_iter()
+            .map(|(label, entry)| with_group(output, entry, Value::String(label)))
+            .collect();
+    }
+
+    // Group the labels of every entry, keeping the order of the first occurrence of the entries.
+    let mut labels_by_entry: IndexMap<String, (&Value, Vec<Value>)> = IndexMap::new();
+    for (label, entry) in entries {
+        let (_, labels) = labels_by_entry
+            .entry(entry.to_string())
+            .or_insert_with(|| (entry, Vec::new()));
+        if !labels.contains(&Value::String(label.clone())) {
+            labels.push(Value::String(label));
+        }
+    }
+    labels_by_entry
+        .into_values()
+        .map(|(entry, labels)| with_group(output, entry, Value::Array(labels)))
+        .collect()
+}
+
+/// Adds the entries of a list of groups, and recursively of their child groups, with the label of their group.
+fn collect_entries<'a>(
+    groups: &'a [Value],
+    keys: &OutputKeys,
+    entries: &mut Vec<(String, &'a Value)>,
+) {
+    for group in groups {
+        let label = group[&keys.group].as_str().unwrap_or_default();
+        if let Some(Value::Array(group_entries)) = group.get(&keys.entries) {
+            entries.extend(group_entries.iter().map(|entry| (label.to_string(), entry)));
+        }
+        if let Some(Value::Array(children)) = group.get(&keys.children) {
+            collect_entries(children, keys, entries);
+        }
+    }
+}
+
+/// Returns a copy of an entry with its group label or labels under the `flat_group_key` key.
+fn with_group(output: &OutputConfig, entry: &Value, group: Value) -> Value {
+    let mut entry = entry.clone();
+    if let Value::Object(object) = &mut entry {
+        object.insert(output.flat_group_key.clone(), group);
+    }
+    entry
+}
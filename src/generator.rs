@@ -0,0 +1,2255 @@
+// This file contains the generation pipeline of the friend links data,
+// from fetching the issues to generating the JSON data of the groups.
+
+use crate::attachment;
+use crate::computed;
+use crate::config::{
+    self, Config, DuplicatePolicy, GenerationConfig, GroupBy, GroupConfig, GroupSort, InputFormat,
+    OutputConfig, SortMode,
+};
+use crate::edit_check::{self, EditedEntry};
+use crate::extract::{self, ExtractError, ExtractOptions};
+use crate::fetch_progress::FetchProgress;
+use crate::github_api::{self, FetchError};
+use crate::github_api_responses::{self, Label};
+use crate::github_auth;
+use crate::group_output::GroupOutput;
+use crate::issue_cache::IssueCache;
+use crate::label_check;
+use crate::link_check::{self, LinkCheck};
+use crate::link_entry::LinkEntry;
+use crate::logging::{self, log_info, log_warn, Level};
+use crate::milestones;
+use crate::rejection::Rejection;
+use crate::sanitize;
+use crate::slug;
+use crate::static_entries;
+use crate::transform;
+use chrono::{DateTime, SecondsFormat, Utc};
+use indexmap::IndexMap;
+use reqwest::header::{ACCEPT, AUTHORIZATION, USER_AGENT};
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::Path;
+
+/// The result of a run of the generation pipeline.
+pub struct Generation {
+    /// The generated data of the groups, as written to the output files.
+    pub groups: Vec<GroupOutput>,
+    /// The entries of each group, by the primary label of the group, in the order of the groups in the configuration.
+    pub entries_by_group: IndexMap<String, Vec<LinkEntry>>,
+    /// The number of issues fetched from the repository, including the pull requests.
+    pub issues_fetched: usize,
+    /// The number of valid entries.
+    pub valid_entries: usize,
+    /// The number of valid entries (active or not) carrying each label, by the name of the label.
+    pub label_counts: BTreeMap<String, usize>,
+    /// The number of active entries, after the duplicates are handled.
+    pub active_entries: usize,
+    /// The issues rejected during the run.
+    pub rejections: Vec<Rejection>,
+    /// The active entries matching none of the groups, which are not included in the generated data.
+    pub ungrouped: Vec<LinkEntry>,
+    /// The results of the checks of the links of the active entries, empty if the link checking is disabled.
+    pub link_checks: Vec<LinkCheck>,
+    /// The results of the checks of the avatars of the active entries, empty if the avatar checking is disabled.
+    pub avatar_checks: Vec<LinkCheck>,
+    /// The active entries whose issue was edited after its approval, empty if the edit checking is disabled.
+    pub edited_entries: Vec<EditedEntry>,
+}
+
+/// This function runs the whole generation pipeline: it checks the configured labels (see `label_check`),
+/// fetches the issues of the configured repository, validates, filters, sorts and groups the entries,
+/// and generates the JSON data of the groups.
+///
+/// The GitHub token is read from the `GITHUB_TOKEN` environment variable.
+///
+/// ## Arguments
+/// - `config`: A reference to the `Config` struct used for the generation.
+///
+/// ## Returns
+/// The generated data and the statistics of the run, or a `FetchError` if the issues could not be fetched.
+pub async fn run(config: &Config) -> Result<Generation, FetchError> {
+    let repository_labels = label_check::fetch_labels(config).await;
+    if let Some(repository_labels) = &repository_labels {
+        label_check::check_labels(config, repository_labels);
+    }
+    let issues = fetch_issues(config).await?;
+    Ok(run_with_issues(config, issues, &repository_labels.unwrap_or_default()).await)
+}
+
+/// This function fetches the issues of the configured repository, or only the changed ones in incremental mode,
+/// so that they can be processed by several generations (see `run_with_issues`).
+///
+/// With `generation.since`, the API only returns the issues updated since the date,
+/// which includes all the issues created since then.
+///
+/// ## Arguments
+/// - `config`: A reference to the `Config` struct that contains the GitHub and fetching options.
+///
+/// When `github.issues_file` is set, the issues are read from the file instead (see `read_issues_file`).
+///
+/// ## Returns
+/// The fetched issues, including the pull requests, or a `FetchError` if they could not be fetched.
+pub async fn fetch_issues(config: &Config) -> Result<Vec<github_api_responses::Issue>, FetchError> {
+    if let Some(path) = &config.github.issues_file {
+        return read_issues_file(path);
+    }
+    let issues = if config.generation.incremental {
+        get_incremental_issues(config).await?
+    } else {
+        let since = config
+            .generation
+            .since
+            .as_deref()
+            .and_then(config::parse_date)
+            .map(|since| since.to_rfc3339_opts(SecondsFormat::Secs, true));
+        get_all_issues(config, since.as_deref(), false).await?
+    };
+    let mut fields = serde_json::Map::new();
+    fields.insert("count".to_string(), issues.len().into());
+    logging::event(
+        Level::Info,
+        Some("issues_fetched"),
+        &format!("Fetched {} issues in total.", issues.len()),
+        fields,
+    );
+    Ok(issues)
+}
+
+/// This function reads the issues from a JSON file of issues, as returned by the issues endpoint of the
+/// GitHub API (e.g. a saved response), instead of fetching them.
+///
+/// ## Arguments
+/// - `path`: The path of the file.
+///
+/// ## Returns
+/// The issues of the file, or a `FetchError` if the file could not be read or parsed.
+fn read_issues_file(path: &str) -> Result<Vec<github_api_responses::Issue>, FetchError> {
+    let content = std::fs::read_to_string(path).map_err(|e| FetchError::IssuesFile {
+        path: path.to_string(),
+        error: e,
+    })?;
+    let issues: Vec<github_api_responses::Issue> = github_api::parse_list(&content)?;
+    let mut fields = serde_json::Map::new();
+    fields.insert("count".to_string(), issues.len().into());
+    fields.insert("path".to_string(), path.into());
+    logging::event(
+        Level::Info,
+        Some("issues_fetched"),
+        &format!("Read {} issues from {}.", issues.len(), path),
+        fields,
+    );
+    Ok(issues)
+}
+
+/// This function runs the generation pipeline on already fetched issues (see `fetch_issues`):
+/// it validates, filters, sorts and groups the entries, and generates the JSON data of the groups.
+///
+/// ## Arguments
+/// - `config`: A reference to the `Config` struct used for the generation.
+/// - `issues`: The fetched issues, including the pull requests.
+/// - `repository_labels`: The labels of the repository (see `label_check::fetch_labels`), used for the descriptions
+///   and colors of the groups, including the groups without entries, or an empty list to only use the labels
+///   of the issues of the entries.
+///
+/// ## Returns
+/// The generated data and the statistics of the run.
+pub async fn run_with_issues(
+    config: &Config,
+    issues: Vec<github_api_responses::Issue>,
+    repository_labels: &[Label],
+) -> Generation {
+    let issues_fetched = issues.len();
+
+    // Filter the issues to only get valid ones based on the specified criteria.
+    let mut rejections: Vec<Rejection> = Vec::new();
+    let entries = get_valid_entries(config, issues, &mut rejections).await;
+    let valid_entries = entries.len();
+    let label_counts = count_entry_labels(&entries);
+
+    // Filter the entries to get only the active ones based on the specified label.
+    let entries = get_all_active_entries(&config.generation, entries);
+
+    // Detect and handle the duplicate submissions.
+    let mut entries = remove_duplicate_entries(&config.generation, entries, &mut rejections);
+
+    // Flag the entries whose issue was edited after its approval, if enabled and the repository is reached,
+    // withholding them from the generated data if requested.
+    let edited_entries = if config.generation.check_edits && config.github.issues_file.is_none() {
+        let edited_entries = edit_check::check_edits(config, &entries).await;
+        for edited in &edited_entries {
+            let message = format!(
+                "Entry edited after its approval: the issue #{} was updated at {}, after the generation label was added at {}, review it again.",
+                edited.number, edited.updated_at, edited.approved_at
+            );
+            if config.generation.withhold_edited {
+                let rejection = Rejection {
+                    id: edited.id,
+                    reason: "edited_after_approval",
+                    message,
+                };
+                reject(&mut rejections, rejection.message.clone(), rejection);
+            } else {
+                let mut fields = serde_json::Map::new();
+                fields.insert("id".to_string(), edited.id.into());
+                fields.insert("number".to_string(), edited.number.into());
+                logging::event(
+                    Level::Warn,
+                    Some("entry_edited_after_approval"),
+                    &format!("{} ID: {}", message, edited.id),
+                    fields,
+                );
+            }
+        }
+        if config.generation.withhold_edited {
+            entries.retain(|entry| !edited_entries.iter().any(|edited| edited.id == entry.id));
+        }
+        edited_entries
+    } else {
+        if config.generation.check_edits {
+            log_warn!("Warning: The issues are read from a file, skipping the edit check.");
+        }
+        Vec::new()
+    };
+
+    // Add the static entries, if any, which are always active.
+    let mut entries = match &config.generation.static_entries_file {
+        Some(path) => {
+            let static_entries = static_entries::load_static_entries(&config.generation, path);
+            static_entries::merge_static_entries(&config.generation, entries, static_entries)
+        }
+        None => entries,
+    };
+
+    // Check that the avatars of the entries are actual images, if enabled,
+    // dropping the entries whose avatar is not if requested.
+    let avatar_checks = if config.link_check.check_avatars {
+        let avatar_checks =
+            link_check::check_avatars(&config.link_check, &config.network.user_agent(), &entries)
+                .await;
+        let invalid_ids: HashSet<usize> = avatar_checks
+            .iter()
+            .filter(|check| !check.alive)
+            .map(|check| check.id)
+            .collect();
+        for check in avatar_checks.iter().filter(|check| !check.alive) {
+            let message = format!(
+                "Invalid avatar {}: {}.",
+                check.url,
+                check.error.as_deref().unwrap_or_default()
+            );
+            if config.link_check.drop_invalid_avatars {
+                let rejection = Rejection {
+                    id: check.id,
+                    reason: "invalid_avatar",
+                    message,
+                };
+                reject(&mut rejections, rejection.message.clone(), rejection);
+            } else {
+                log_warn!("{} ID: {}", message, check.id);
+            }
+        }
+        if config.link_check.drop_invalid_avatars {
+            entries.retain(|entry| !invalid_ids.contains(&entry.id));
+        }
+        avatar_checks
+    } else {
+        Vec::new()
+    };
+    let active_entries = entries.len();
+
+    // Sort the entries, the order is kept within each group.
+    sort_entries(&config.generation, None, &mut entries);
+
+    // Assign the slugs to the entries, if enabled.
+    if config.output.include_slug {
+        assign_slugs(&config.output, &mut entries);
+    }
+
+    // Check the links of the entries, if enabled.
+    let link_checks = if config.link_check.enabled {
+        let link_checks =
+            link_check::check_links(&config.link_check, &config.network.user_agent(), &entries)
+                .await;
+        for check in link_checks.iter().filter(|check| !check.alive) {
+            log_warn!(
+                "Dead link {} ({}) after {} attempts, ID: {}",
+                check.url,
+                check.error.as_deref().unwrap_or_default(),
+                check.attempts,
+                check.id
+            );
+        }
+        link_checks
+    } else {
+        Vec::new()
+    };
+
+    // Never include the draft entries, unless they are requested.
+    let entries: Vec<LinkEntry> = entries
+        .into_iter()
+        .filter(|entry| {
+            let skipped = entry.draft && !config.generation.include_drafts;
+            if skipped {
+                log_info!("Skipping draft entry, ID: {}", entry.id);
+            }
+            !skipped
+        })
+        .collect();
+
+    // Group the entries by the labels of the configured groups, or by milestone.
+    let (group_to_entry_map, ungrouped) = match config.generation.group_by {
+        GroupBy::Labels => group_by_labels(config, entries),
+        GroupBy::Milestone => (milestones::group_by_milestone(entries), Vec::new()),
+    };
+    // Print the grouped issues.
+    log_info!("\nGrouped Issues:");
+    for (group_label, issues) in &group_to_entry_map {
+        log_info!("Group: {}", group_label);
+        for issue in issues {
+            log_info!("  - Entry ID: {}", issue.id);
+            log_info!("    Entry Data: {}", issue.json_data);
+        }
+    }
+
+    // Generate the JSON output from the grouped issues.
+    let groups = match config.generation.group_by {
+        GroupBy::Labels => generate_json(config, &group_to_entry_map, repository_labels),
+        GroupBy::Milestone => milestones::generate_milestone_json(&group_to_entry_map, |entry| {
+            generate_entry_json(&config.output, entry)
+        }),
+    };
+
+    Generation {
+        groups,
+        entries_by_group: group_to_entry_map,
+        issues_fetched,
+        valid_entries,
+        label_counts,
+        active_entries,
+        rejections,
+        ungrouped,
+        link_checks,
+        avatar_checks,
+        edited_entries,
+    }
+}
+
+/// This function counts the labels of the valid entries (active or not) of already fetched issues
+/// (see `fetch_issues`), for `--list-labels`.
+///
+/// Only the validation steps of the pipeline are run: the entries are neither checked (edits, avatars, links)
+/// nor grouped, and no data is generated.
+///
+/// ## Arguments
+/// - `config`: A reference to the `Config` struct used for the generation.
+/// - `issues`: The fetched issues, including the pull requests.
+///
+/// ## Returns
+/// The number of valid entries carrying each label, by the name of the label.
+pub async fn count_labels(
+    config: &Config,
+    issues: Vec<github_api_responses::Issue>,
+) -> BTreeMap<String, usize> {
+    let mut rejections: Vec<Rejection> = Vec::new();
+    let entries = get_valid_entries(config, issues, &mut rejections).await;
+    count_entry_labels(&entries)
+}
+
+/// This function runs the validation steps of the pipeline on the fetched issues: it filters them by date,
+/// canonicalizes their labels, skips the pull requests, downloads the JSON attachments if enabled,
+/// and keeps the valid entries.
+///
+/// ## Arguments
+/// - `config`: A reference to the `Config` struct used for the generation.
+/// - `issues`: The fetched issues, including the pull requests.
+/// - `rejections`: The rejected issues, to which the invalid ones are added.
+///
+/// ## Returns
+/// The valid entries, active or not.
+async fn get_valid_entries(
+    config: &Config,
+    issues: Vec<github_api_responses::Issue>,
+    rejections: &mut Vec<Rejection>,
+) -> Vec<LinkEntry> {
+    // Only keep the issues created (or updated) since the date, if set.
+    let since = config
+        .generation
+        .since
+        .as_deref()
+        .and_then(config::parse_date);
+    let issues = match since {
+        Some(since) => filter_issues_since(&config.generation, issues, since),
+        None => issues,
+    };
+
+    // Use the configured spelling of the labels of the issues, if they are matched ignoring case.
+    let mut issues = issues;
+    if config.generation.case_insensitive_labels {
+        canonicalize_labels(config, &mut issues);
+    }
+
+    // Skip the pull requests, which are also returned by the issues endpoint.
+    let issues: Vec<github_api_responses::Issue> = issues
+        .into_iter()
+        .filter(|issue| {
+            if issue.pull_request.is_some() {
+                log_info!("Skipping pull request, ID: {}", issue.id);
+            }
+            issue.pull_request.is_none()
+        })
+        .collect();
+
+    // Download the JSON attachments, if enabled, and only keep the valid issues.
+    let attachments = if config.generation.json_attachments
+        && config.generation.input_format == InputFormat::Json
+    {
+        attachment::download_attachments(config, &issues).await
+    } else {
+        HashMap::new()
+    };
+    get_all_valid_issues(&config.generation, issues, &attachments, rejections)
+}
+
+/// This function counts the entries carrying each label.
+///
+/// ## Arguments
+/// - `entries`: The entries whose labels are counted.
+///
+/// ## Returns
+/// The number of entries carrying each label, by the name of the label.
+fn count_entry_labels(entries: &[LinkEntry]) -> BTreeMap<String, usize> {
+    let mut label_counts: BTreeMap<String, usize> = BTreeMap::new();
+    for label in entries.iter().flat_map(|entry| &entry.labels) {
+        *label_counts.entry(label.name.clone()).or_default() += 1;
+    }
+    label_counts
+}
+
+/// This function groups the entries based on the groups defined in the configuration,
+/// keeping the order of the groups so that the logs and the output are stable across runs.
+/// An entry matching the labels of multiple groups is added to all of them.
+///
+/// ## Arguments
+/// - `config`: A reference to the `Config` struct that contains the groups.
+/// - `entries`: The entries to be grouped.
+///
+/// ## Returns
+/// The entries of every group, mapped to the primary label of the group,
+/// and the entries that match none of the groups.
+fn group_by_labels(
+    config: &Config,
+    entries: Vec<LinkEntry>,
+) -> (IndexMap<String, Vec<LinkEntry>>, Vec<LinkEntry>) {
+    let mut group_to_entry_map: IndexMap<String, Vec<LinkEntry>> = config
+        .groups
+        .iter()
+        .map(|group| (group.primary_label().to_string(), Vec::new()))
+        .collect();
+    let mut ungrouped: Vec<LinkEntry> = Vec::new();
+    // Process each issue.
+    for entry in entries {
+        // Check if the issue has any of the labels of each group.
+        // An issue matching the labels of multiple groups is added to all of them.
+        let mut grouped = false;
+        for group in &config.groups {
+            if group.matches(|label| entry.has_label(label)) {
+                // If it does, add the issue to the corresponding group.
+                group_to_entry_map
+                    .entry(group.primary_label().to_string())
+                    .or_default()
+                    .push(entry.clone());
+                grouped = true;
+            }
+        }
+        // Report the issues that would otherwise silently be missing from the output.
+        if !grouped {
+            let labels: Vec<&str> = entry
+                .labels
+                .iter()
+                .map(|label| label.name.as_str())
+                .collect();
+            log_warn!(
+                "Entry {} matches no group and is not included, labels: {}",
+                entry.id,
+                labels.join(", ")
+            );
+            ungrouped.push(entry);
+        }
+    }
+    (group_to_entry_map, ungrouped)
+}
+
+/// This function runs the whole generation pipeline (see `run`), and only returns the generated data of the groups.
+///
+/// ## Arguments
+/// - `config`: A reference to the `Config` struct used for the generation.
+///
+/// ## Returns
+/// The generated data of the groups, or a `FetchError` if the issues could not be fetched.
+pub async fn generate(config: &Config) -> Result<Vec<GroupOutput>, FetchError> {
+    run(config).await.map(|generation| generation.groups)
+}
+
+/// The number of issues requested per page from the GitHub API (the maximum allowed by the API).
+const ISSUES_PER_PAGE: usize = 100;
+
+/// This function retrieves all issues from a specified GitHub repository.
+/// It uses the GitHub API to fetch issues page by page, until a page with fewer
+/// than `ISSUES_PER_PAGE` issues is returned, and returns all of them.
+///
+/// ## Arguments
+/// - `config`: A reference to a `Config` struct that contains the GitHub API URL, owner, repository name,
+///   and network options.
+///
+/// See: https://docs.github.com/en/rest/issues/issues?apiVersion=2022-11-28#list-repository-issues
+/// - `since`: If set, only the issues updated at or after this time are retrieved.
+///   Note that the API filters by the updated time, not the creation time.
+/// - `include_closed`: Whether to also retrieve the closed issues, otherwise only the open issues are retrieved.
+///
+/// When `network.resume_pages` is enabled, the progress is saved after every page, and a fetch interrupted
+/// less than `network.resume_window_seconds` ago is resumed after its last page, unless the first page changed.
+async fn get_all_issues(
+    config: &Config,
+    since: Option<&str>,
+    include_closed: bool,
+) -> Result<Vec<github_api_responses::Issue>, FetchError> {
+    // Setup the Reqwest client.
+    let client = github_api::github_client(&config.network)?;
+    // Get the GitHub token, from the environment variables or minted for the GitHub App.
+    let github_token = github_auth::github_token(config, &client).await?;
+    // Construct the URL for the GitHub API request.
+    let url = format!(
+        "{}/repos/{}/{}/issues",
+        config.github.api_url.trim_end_matches('/'),
+        config.github.owner,
+        config.github.repository
+    );
+
+    // Load the progress of an interrupted fetch, if resuming is enabled.
+    let progress_path = Path::new(&config.network.progress_file);
+    let mut saved_progress = if config.network.resume_pages {
+        FetchProgress::load(
+            progress_path,
+            &url,
+            since,
+            include_closed,
+            config.network.resume_window_seconds,
+        )
+    } else {
+        None
+    };
+    let mut first_page_ids: Vec<usize> = Vec::new();
+    let mut resumed = false;
+
+    let mut issues: Vec<github_api_responses::Issue> = Vec::new();
+    let mut page = 1;
+    loop {
+        let page_issues = get_issues_page(
+            config,
+            &client,
+            &url,
+            &github_token,
+            page,
+            since,
+            include_closed,
+        )
+        .await?;
+        let page_len = page_issues.len();
+        log_info!("Fetched {} issues from page {}.", page_len, page);
+
+        // The first page is always fetched again, since a new or removed issue shifts all the pages:
+        // the interrupted fetch is only resumed if its first page is the same.
+        if page == 1 {
+            first_page_ids = page_issues.iter().map(|issue| issue.id).collect();
+            if let Some(progress) = saved_progress.take() {
+                if progress.first_page_ids == first_page_ids {
+                    log_info!(
+                        "Resuming the interrupted fetch of the issues after page {}.",
+                        progress.pages
+                    );
+                    issues = progress.issues;
+                    page = progress.pages + 1;
+                    resumed = true;
+                    continue;
+                }
+                log_info!(
+                    "The issues changed since the interrupted fetch, fetching all the pages again."
+                );
+            }
+        }
+        if resumed {
+            // Skip the issues already fetched, in case an issue was moved to another page.
+            let fetched_ids: HashSet<usize> = issues.iter().map(|issue| issue.id).collect();
+            issues.extend(
+                page_issues
+                    .into_iter()
+                    .filter(|issue| !fetched_ids.contains(&issue.id)),
+            );
+        } else {
+            issues.extend(page_issues);
+        }
+
+        // A page that is not full means that there are no more issues to fetch.
+        if page_len < ISSUES_PER_PAGE {
+            break;
+        }
+        if config.network.resume_pages {
+            FetchProgress {
+                url: url.clone(),
+                since: since.map(str::to_string),
+                include_closed,
+                saved_at: Utc::now().to_rfc3339(),
+                first_page_ids: first_page_ids.clone(),
+                pages: page,
+                issues: issues.clone(),
+            }
+            .save(progress_path);
+        }
+        page += 1;
+    }
+    if config.network.resume_pages {
+        FetchProgress::clear(progress_path);
+    }
+
+    Ok(issues)
+}
+
+/// This function retrieves the issues for the incremental generation: on the first run all the open issues
+/// are retrieved, and on the next runs only the issues updated since the last run are retrieved and merged
+/// into the cached issues. The cache is then saved for the next run.
+///
+/// ## Arguments
+/// - `config`: A reference to a `Config` struct that contains the path of the cache file.
+///
+/// ## Returns
+/// All the open issues of the repository.
+async fn get_incremental_issues(
+    config: &Config,
+) -> Result<Vec<github_api_responses::Issue>, FetchError> {
+    let cache_path = Path::new(&config.generation.cache_file);
+    let cache = IssueCache::load(cache_path).and_then(|cache| {
+        let since = cache.last_updated_at.clone()?;
+        Some((cache, since))
+    });
+
+    let cache = match cache {
+        Some((mut cache, since)) => {
+            log_info!("Fetching the issues updated since {}.", since);
+            // The closed issues are also needed to remove them from the cache.
+            let changed = get_all_issues(config, Some(&since), true).await?;
+            cache.merge(changed);
+            cache
+        }
+        None => {
+            log_info!("No issue cache found, fetching all the issues.");
+            let mut cache = IssueCache::default();
+            cache.merge(get_all_issues(config, None, false).await?);
+            cache
+        }
+    };
+    cache.save(cache_path);
+
+    Ok(cache.issues)
+}
+
+/// This function retrieves a single page of issues from the GitHub API,
+/// retrying the request when it fails temporarily.
+///
+/// ## Arguments
+/// - `config`: A reference to a `Config` struct that contains the network options.
+/// - `client`: The Reqwest client used to send the request.
+/// - `url`: The URL of the issues endpoint of the repository.
+/// - `github_token`: The GitHub token used to authenticate the request.
+/// - `page`: The number of the page to retrieve, starting from 1.
+/// - `since`: If set, only the issues updated at or after this time are retrieved.
+/// - `include_closed`: Whether to also retrieve the closed issues.
+async fn get_issues_page(
+    config: &Config,
+    client: &reqwest::Client,
+    url: &str,
+    github_token: &str,
+    page: usize,
+    since: Option<&str>,
+    include_closed: bool,
+) -> Result<Vec<github_api_responses::Issue>, FetchError> {
+    // Build the GET request to the GitHub API.
+    let mut request = client
+        .get(url)
+        .query(&[("per_page", ISSUES_PER_PAGE), ("page", page)]);
+    if include_closed {
+        request = request.query(&[("state", "all")]);
+    }
+    if let Some(since) = since {
+        request = request.query(&[("since", since)]);
+    }
+    let request = request
+        .header(USER_AGENT, config.network.user_agent())
+        .header(ACCEPT, "application/vnd.github+json")
+        .header(AUTHORIZATION, format!("Bearer {}", github_token))
+        .header("X-GitHub-Api-Version", "2022-11-28");
+
+    // Send the request, retrying it if needed, and read the response body.
+    let res = github_api::send_with_retry(&config.network, request).await?;
+    let body = res.text().await.map_err(FetchError::Request)?;
+
+    // Deserialize the response body into a vector of `Issue` structs and return it.
+    github_api::parse_list(&body)
+}
+
+/// This function only keeps the issues created at or after a date, or updated at or after it
+/// when the entries are sorted by their updated time.
+///
+/// The issues whose timestamp is malformed are kept, so that they are rejected with the other invalid issues.
+///
+/// ## Arguments
+/// - `generation`: A reference to the `GenerationConfig` struct that tells which timestamp is compared.
+/// - `issues`: A vector of `Issue` structs representing the issues to be filtered.
+/// - `since`: The date the issues must be created or updated at or after.
+///
+/// ## Returns
+/// The issues created (or updated) at or after the date.
+fn filter_issues_since(
+    generation: &GenerationConfig,
+    issues: Vec<github_api_responses::Issue>,
+    since: DateTime<Utc>,
+) -> Vec<github_api_responses::Issue> {
+    issues
+        .into_iter()
+        .filter(|issue| {
+            let timestamp = if generation.sort_by_updated_time {
+                issue.updated_at()
+            } else {
+                issue.created_at()
+            };
+            let keep = timestamp.map_or(true, |timestamp| timestamp >= since);
+            if !keep {
+                log_info!("Skipping issue older than {}, ID: {}", since, issue.id);
+            }
+            keep
+        })
+        .collect()
+}
+
+/// This function renames the labels of the issues that only differ by case from a configured label
+/// to the configured spelling, so that all the label matching (the active filter, the grouping,
+/// the label flags, etc.) ignores case. Every renamed spelling is logged once.
+///
+/// ## Arguments
+/// - `config`: A reference to the `Config` struct that contains the configured labels.
+/// - `issues`: The issues whose labels are renamed in place.
+fn canonicalize_labels(config: &Config, issues: &mut [github_api_responses::Issue]) {
+    let configured = config.configured_labels();
+    let mut renamed: HashSet<(String, String)> = HashSet::new();
+    for label in issues.iter_mut().flat_map(|issue| issue.labels.iter_mut()) {
+        let Some(canonical) = configured
+            .iter()
+            .find(|configured| configured.to_lowercase() == label.name.to_lowercase())
+        else {
+            continue;
+        };
+        if label.name != *canonical {
+            let name = std::mem::replace(&mut label.name, canonical.to_string());
+            if renamed.insert((name.clone(), canonical.to_string())) {
+                log_info!("Matching label \"{}\" as \"{}\".", name, canonical);
+            }
+        }
+    }
+}
+
+/// This function filters the issues, based on the content of the issue body
+/// and criteria described in the design documentation, and returns a vector
+/// that only contains issues with valid data to be processed.
+///
+/// See `extract::extract_entry_json` for the criteria an issue body must meet,
+/// or `extract::extract_form_entry` when the issues are created with an issue form.
+/// The JSON file attached to an issue without data comments, if downloaded, is used instead of its body.
+///
+/// ## Arguments
+/// - `generation`: A reference to the `GenerationConfig` struct that contains the parsing options.
+/// - `issues`: A vector of `Issue` structs representing the issues to be filtered.
+/// - `attachments`: The content of the JSON files attached to the issues, or the reason why they could not be
+///   downloaded, by the ID of the issue.
+/// - `rejections`: The list the invalid issues are added to, with the reason of their rejection.
+///
+/// ## Returns
+/// A vector of `LinkEntry` structs that contains the data, representing the friend links entries,
+/// retrieved from the valid issues.
+fn get_all_valid_issues(
+    generation: &GenerationConfig,
+    issues: Vec<github_api_responses::Issue>,
+    attachments: &HashMap<usize, Result<String, String>>,
+    rejections: &mut Vec<Rejection>,
+) -> Vec<LinkEntry> {
+    let mut entries: Vec<LinkEntry> = Vec::new();
+    let options = ExtractOptions {
+        lenient_json: generation.lenient_json,
+        allow_other_content: generation.allow_other_content,
+    };
+
+    for issue in issues {
+        log_info!("Checking issue, ID: {}", issue.id);
+
+        // Skip the issues opened by users who are not on the list of allowed authors, if set.
+        if !generation.allowed_authors.is_empty() {
+            let author = issue.user.as_ref().map(|user| user.login.as_str());
+            let allowed = author.is_some_and(|login| {
+                generation
+                    .allowed_authors
+                    .iter()
+                    .any(|allowed| allowed.eq_ignore_ascii_case(login))
+            });
+            if !allowed {
+                let message = format!(
+                    "The author {} is not on the list of allowed authors.",
+                    author.map_or("(unknown)".to_string(), |login| format!("\"{}\"", login))
+                );
+                let rejection = Rejection {
+                    id: issue.id,
+                    reason: "unapproved_author",
+                    message,
+                };
+                reject(rejections, rejection.message.clone(), rejection);
+                continue;
+            }
+        }
+
+        // Extract the JSON data of the entry from the issue body, which may be missing,
+        // without scanning the bodies larger than the limit.
+        let extracted = match issue.body.as_deref() {
+            Some(body)
+                if generation
+                    .max_body_length
+                    .is_some_and(|max| body.len() > max) =>
+            {
+                Err(ExtractError::BodyTooLarge {
+                    length: body.len(),
+                    max_length: generation.max_body_length.unwrap_or_default(),
+                })
+            }
+            Some(body) if !body.trim().is_empty() => match generation.input_format {
+                InputFormat::Json => match attachments.get(&issue.id) {
+                    Some(Ok(content)) => extract::parse_attachment(content, &options),
+                    Some(Err(reason)) => Err(ExtractError::AttachmentFailed(reason.clone())),
+                    None => extract::extract_entry_json(body, &options),
+                },
+                InputFormat::Form => extract::extract_form_entry(body, &generation.form_fields),
+            },
+            _ => Err(ExtractError::EmptyBody),
+        };
+        let mut json_data = match extracted {
+            Ok(extracted) => {
+                if extracted.lenient_fixed {
+                    log_info!("Comments or trailing commas were removed from the JSON data.");
+                }
+                extracted.json_data
+            }
+            Err(e) => {
+                let rejection = Rejection {
+                    id: issue.id,
+                    reason: e.code(),
+                    message: e.to_string(),
+                };
+                reject(rejections, rejection.message.clone(), rejection);
+                continue;
+            }
+        };
+
+        // Map the submitted fields to the fields of the generated data.
+        transform::transform_fields(generation, issue.id, Some(&issue.title), &mut json_data);
+
+        // Parse the timestamps of the issue, rejecting it if one of them is malformed.
+        let (created_at, updated_at) = match (issue.created_at(), issue.updated_at()) {
+            (Ok(created_at), Ok(updated_at)) => (created_at, updated_at),
+            (created_at, _) => {
+                let (field, value) = if created_at.is_err() {
+                    ("created_at", &issue.created_at)
+                } else {
+                    ("updated_at", &issue.updated_at)
+                };
+                let message = format!("Malformed {} timestamp: \"{}\".", field, value);
+                let rejection = Rejection {
+                    id: issue.id,
+                    reason: "invalid_timestamp",
+                    message,
+                };
+                reject(rejections, rejection.message.clone(), rejection);
+                continue;
+            }
+        };
+
+        // If all checks passed, create a `LinkEntry` from the issue data.
+        let draft = generation
+            .draft_label
+            .as_ref()
+            .is_some_and(|label| issue.labels.iter().any(|l| l.name == *label));
+        let entry = LinkEntry {
+            id: issue.id,
+            number: Some(issue.number),
+            html_url: issue.html_url.clone(),
+            labels: issue.labels.clone(),
+            milestone: issue.milestone.clone(),
+            json_data,
+            created_at,
+            updated_at,
+            slug: None,
+            draft,
+        };
+
+        // Add the entry to the list of entries.
+        entries.push(entry);
+    }
+
+    entries
+}
+
+/// This function logs a rejected issue, as an `issue_rejected` event with its ID and reason
+/// in the JSON logs, and adds it to the rejected issues.
+///
+/// ## Arguments
+/// - `rejections`: The list the rejected issue is added to.
+/// - `text`: The message logged for the rejection.
+/// - `rejection`: The rejected issue.
+fn reject(rejections: &mut Vec<Rejection>, text: String, rejection: Rejection) {
+    let mut fields = serde_json::Map::new();
+    fields.insert("id".to_string(), rejection.id.into());
+    fields.insert("reason".to_string(), rejection.reason.into());
+    logging::event(Level::Warn, Some("issue_rejected"), &text, fields);
+    rejections.push(rejection);
+}
+
+/// This function returns the list of issue that is active
+/// depending on the provided labels that identify the active issues,
+/// and that carries none of the excluded labels.
+///
+/// ## Arguments
+/// - `generation`: A reference to the `GenerationConfig` struct that contains the labels
+///   that identify the active issues, how they are matched, and the excluded labels.
+/// - `issues`: A vector of `Issue` structs representing the issues to be filtered.
+///
+/// ## Returns
+/// A vector of `Issue` structs that contains all active issues (i.e. with the specified labels
+/// and without any excluded label).
+fn get_all_active_entries(generation: &GenerationConfig, issues: Vec<LinkEntry>) -> Vec<LinkEntry> {
+    issues
+        .into_iter()
+        .filter(|issue| {
+            generation
+                .label_match
+                .matches(&generation.label, |label| issue.has_label(label))
+        })
+        .filter(|issue| {
+            let excluded = generation
+                .exclude_labels
+                .iter()
+                .any(|label| issue.has_label(label));
+            if excluded {
+                log_info!("Excluding entry, ID: {}", issue.id);
+            }
+            !excluded
+        })
+        .collect()
+}
+
+/// Returns the identity of an entry from the value of its identity field, as compared by the duplicate
+/// detection and the changes report: a string value is trimmed, without trailing slashes and in lowercase,
+/// any other value (e.g. a number) is compared as its JSON text, and a missing or `null` value has none.
+pub fn identity_value(value: Option<&serde_json::Value>) -> Option<String> {
+    match value {
+        Some(serde_json::Value::String(value)) => {
+            Some(value.trim().trim_end_matches('/').to_lowercase())
+        }
+        Some(serde_json::Value::Null) | None => None,
+        Some(value) => Some(value.to_string()),
+    }
+}
+
+/// Returns the value of the duplicate key of an entry, compared to detect the duplicates (see `identity_value`).
+pub(crate) fn duplicate_key_value(
+    generation: &GenerationConfig,
+    entry: &LinkEntry,
+) -> Option<String> {
+    identity_value(entry.json_data.get(generation.duplicate_identity_field()))
+}
+
+/// This function detects the entries that have the same value for the configured duplicate key
+/// (`identity_field` if it is set, e.g. the same `url`), logs the colliding issue IDs, and handles
+/// them according to the configured `DuplicatePolicy`.
+///
+/// String values are compared ignoring case, surrounding whitespace and trailing slashes.
+/// Entries without the duplicate key are never considered duplicates. When the colliding entries
+/// have the same update time (`keep_latest`) or creation time (`reject`), the one with the lowest ID is kept.
+///
+/// ## Arguments
+/// - `generation`: A reference to the `GenerationConfig` struct that contains the duplicate key and policy.
+/// - `entries`: The entries to be checked, which should not be grouped yet,
+///   so that an entry in multiple groups is not detected as a duplicate.
+/// - `rejections`: The list the rejected duplicate entries are added to.
+///
+/// ## Returns
+/// The entries that are kept, in their original order.
+fn remove_duplicate_entries(
+    generation: &GenerationConfig,
+    entries: Vec<LinkEntry>,
+    rejections: &mut Vec<Rejection>,
+) -> Vec<LinkEntry> {
+    // Map each duplicate key value to the indices of the entries that have it.
+    let mut key_to_indices: HashMap<String, Vec<usize>> = HashMap::new();
+    for (index, entry) in entries.iter().enumerate() {
+        if let Some(key) = duplicate_key_value(generation, entry) {
+            key_to_indices.entry(key).or_default().push(index);
+        }
+    }
+
+    // Only keep the collisions, in the order of their first entry, so that the logs are deterministic.
+    let mut collisions: Vec<(String, Vec<usize>)> = key_to_indices
+        .into_iter()
+        .filter(|(_, indices)| indices.len() > 1)
+        .collect();
+    collisions.sort_by_key(|(_, indices)| indices[0]);
+
+    let mut removed: HashSet<usize> = HashSet::new();
+    for (key, indices) in &collisions {
+        let ids: Vec<String> = indices.iter().map(|&i| entries[i].id.to_string()).collect();
+        log_info!(
+            "Duplicate entries found for {} \"{}\", IDs: {}",
+            generation.duplicate_identity_field(),
+            key,
+            ids.join(", ")
+        );
+
+        let kept = match generation.on_duplicate {
+            DuplicatePolicy::KeepAll => continue,
+            // On equal times, the entry with the lowest ID (i.e. the earliest issue) is kept.
+            DuplicatePolicy::KeepLatest => indices
+                .iter()
+                .copied()
+                .max_by_key(|&i| (entries[i].updated_at, Reverse(entries[i].id))),
+            DuplicatePolicy::Reject => indices
+                .iter()
+                .copied()
+                .min_by_key(|&i| (entries[i].created_at, entries[i].id)),
+        };
+        for &index in indices {
+            if Some(index) != kept {
+                match generation.on_duplicate {
+                    DuplicatePolicy::Reject => {
+                        let rejection = Rejection {
+                            id: entries[index].id,
+                            reason: "duplicate",
+                            message: format!(
+                                "Duplicate of another entry with the same {}.",
+                                generation.duplicate_identity_field()
+                            ),
+                        };
+                        let text = format!("Rejecting duplicate entry, ID: {}", rejection.id);
+                        reject(rejections, text, rejection);
+                    }
+                    _ => log_info!("Dropping older duplicate entry, ID: {}", entries[index].id),
+                }
+                removed.insert(index);
+            }
+        }
+    }
+
+    entries
+        .into_iter()
+        .enumerate()
+        .filter(|(index, _)| !removed.contains(index))
+        .map(|(_, entry)| entry)
+        .collect()
+}
+
+/// This function sorts the entries by their creation time or updated time,
+/// and then alphabetically by a field if enabled, depending on the generation configuration
+/// and the sort override of a group, if any.
+///
+/// ## Arguments
+/// - `generation`: A reference to the `GenerationConfig` struct that contains the sorting options.
+/// - `group_sort`: The sort override of the group the entries belong to, whose options win over
+///   the ones of the generation configuration.
+/// - `entries`: The entries to be sorted in place.
+fn sort_entries(
+    generation: &GenerationConfig,
+    group_sort: Option<&GroupSort>,
+    entries: &mut [LinkEntry],
+) {
+    let sort_by = group_sort
+        .and_then(|sort| sort.sort_by)
+        .unwrap_or(generation.sort_by);
+    let sort_by_updated_time = group_sort
+        .and_then(|sort| sort.sort_by_updated_time)
+        .unwrap_or(generation.sort_by_updated_time);
+    let descending = group_sort
+        .and_then(|sort| sort.descending)
+        .unwrap_or(generation.descending);
+
+    entries.sort_by(|a, b| {
+        let ordering = if sort_by_updated_time {
+            a.updated_at.cmp(&b.updated_at)
+        } else {
+            a.created_at.cmp(&b.created_at)
+        };
+        if descending {
+            ordering.reverse()
+        } else {
+            ordering
+        }
+    });
+
+    // Sort alphabetically by the field if enabled, keeping the time order of the entries with the same value.
+    // The entries missing the field are always last.
+    if sort_by == SortMode::ByField {
+        let field = group_sort
+            .and_then(|sort| sort.sort_field.as_deref())
+            .unwrap_or(&generation.sort_field);
+        if descending {
+            entries.sort_by_cached_key(|entry| {
+                let key = sort_key(entry, field);
+                (key.is_none(), key.map(Reverse))
+            });
+        } else {
+            entries.sort_by_cached_key(|entry| {
+                let key = sort_key(entry, field);
+                (key.is_none(), key)
+            });
+        }
+    }
+}
+
+/// Returns the key used to sort an entry alphabetically by a field, i.e. the trimmed and lowercased
+/// value of the field without its diacritics (see `fold_diacritics`), or its JSON text if it is not a string,
+/// or `None` if the field is missing or blank.
+fn sort_key(entry: &LinkEntry, field: &str) -> Option<String> {
+    let key = match entry.json_data.get(field)? {
+        serde_json::Value::String(value) => fold_diacritics(&value.trim().to_lowercase()),
+        serde_json::Value::Null => return None,
+        value => value.to_string(),
+    };
+    (!key.is_empty()).then_some(key)
+}
+
+/// Folds the diacritics of a lowercased text, so that e.g. "écho" is sorted like "echo", before "zulu":
+/// the combining marks (U+0300 to U+036F) are removed, and the accented Latin letters are replaced
+/// with their base letters. The other characters are kept as is.
+fn fold_diacritics(text: &str) -> String {
+    let mut folded = String::with_capacity(text.len());
+    for c in text.chars() {
+        let base = match c {
+            '\u{0300}'..='\u{036f}' => continue,
+            'à' | 'á' | 'â' | 'ã' | 'ä' | 'å' | 'ā' | 'ă' | 'ą' => "a",
+            'æ' => "ae",
+            'ç' | 'ć' | 'ĉ' | 'ċ' | 'č' => "c",
+            'ð' | 'ď' | 'đ' => "d",
+            'è' | 'é' | 'ê' | 'ë' | 'ē' | 'ĕ' | 'ė' | 'ę' | 'ě' => "e",
+            'ĝ' | 'ğ' | 'ġ' | 'ģ' => "g",
+            'ĥ' | 'ħ' => "h",
+            'ì' | 'í' | 'î' | 'ï' | 'ĩ' | 'ī' | 'ĭ' | 'į' | 'ı' => "i",
+            'ĵ' => "j",
+            'ķ' => "k",
+            'ĺ' | 'ļ' | 'ľ' | 'ŀ' | 'ł' => "l",
+            'ñ' | 'ń' | 'ņ' | 'ň' => "n",
+            'ò' | 'ó' | 'ô' | 'õ' | 'ö' | 'ø' | 'ō' | 'ŏ' | 'ő' => "o",
+            'œ' => "oe",
+            'ŕ' | 'ŗ' | 'ř' => "r",
+            'ś' | 'ŝ' | 'ş' | 'š' => "s",
+            'ß' => "ss",
+            'ţ' | 'ť' | 'ŧ' => "t",
+            'þ' => "th",
+            'ù' | 'ú' | 'û' | 'ü' | 'ũ' | 'ū' | 'ŭ' | 'ů' | 'ű' | 'ų' => "u",
+            'ŵ' => "w",
+            'ý' | 'ÿ' | 'ŷ' => "y",
+            'ź' | 'ż' | 'ž' => "z",
+            _ => {
+                folded.push(c);
+                continue;
+            }
+        };
+        folded.push_str(base);
+    }
+    folded
+}
+
+/// This function inserts a field generated by the script into the JSON data of an entry,
+/// without overwriting a same-named field supplied by the contributor.
+///
+/// ## Arguments
+/// - `entry_id`: The ID of the entry, used for logging.
+/// - `json_data`: The JSON data of the entry to insert the field into.
+/// - `key`: The key of the field.
+/// - `value`: The value of the field.
+fn insert_generated_field(
+    entry_id: usize,
+    json_data: &mut serde_json::Value,
+    key: &str,
+    value: serde_json::Value,
+) {
+    if let Some(object) = json_data.as_object_mut() {
+        if object.contains_key(key) {
+            log_info!(
+                "Entry {} already contains the field \"{}\", keeping the supplied value.",
+                entry_id,
+                key
+            );
+        } else {
+            object.insert(key.to_string(), value);
+        }
+    }
+}
+
+/// This function converts a single link entry into the JSON object written to the output file,
+/// adding the fields generated by the script, as enabled in the output configuration.
+///
+/// ## Arguments
+/// - `output`: A reference to the `OutputConfig` struct that contains the output options.
+/// - `entry`: A reference to the `LinkEntry` to be converted.
+///
+/// ## Returns
+/// The JSON object representing the entry in the output file.
+fn generate_entry_json(output: &OutputConfig, entry: &LinkEntry) -> serde_json::Value {
+    let mut json_data = entry.json_data.clone();
+
+    strip_fields(output, &mut json_data);
+    sanitize_fields(output, entry.id, &mut json_data);
+
+    if output.include_timestamps {
+        insert_generated_field(
+            entry.id,
+            &mut json_data,
+            &output.created_at_key,
+            serde_json::Value::String(entry.created_at.to_rfc3339()),
+        );
+        insert_generated_field(
+            entry.id,
+            &mut json_data,
+            &output.updated_at_key,
+            serde_json::Value::String(entry.updated_at.to_rfc3339()),
+        );
+    }
+
+    if let Some(number) = entry.number.filter(|_| output.include_issue) {
+        insert_generated_field(
+            entry.id,
+            &mut json_data,
+            "issueNumber",
+            serde_json::Value::from(number),
+        );
+        if let Some(html_url) = &entry.html_url {
+            insert_generated_field(
+                entry.id,
+                &mut json_data,
+                "issueUrl",
+                serde_json::Value::String(html_url.clone()),
+            );
+        }
+    }
+
+    normalize_avatar(output, entry.id, &mut json_data);
+
+    for (field, computed) in &output.computed_fields {
+        let source = json_data
+            .get(&computed.source)
+            .and_then(|value| value.as_str());
+        match source.and_then(|value| computed::compute(computed.op, value)) {
+            Some(value) => insert_generated_field(
+                entry.id,
+                &mut json_data,
+                field,
+                serde_json::Value::String(value),
+            ),
+            None => log_warn!(
+                "Cannot compute field {} of entry from {}, skipping it, ID: {}",
+                field,
+                computed.source,
+                entry.id
+            ),
+        }
+    }
+
+    for (label, field) in &output.label_flags {
+        insert_generated_field(
+            entry.id,
+            &mut json_data,
+            field,
+            serde_json::Value::Bool(entry.has_label(label)),
+        );
+    }
+
+    if entry.draft {
+        insert_generated_field(
+            entry.id,
+            &mut json_data,
+            "draft",
+            serde_json::Value::Bool(true),
+        );
+    }
+
+    if let Some(slug) = &entry.slug {
+        insert_generated_field(
+            entry.id,
+            &mut json_data,
+            "slug",
+            serde_json::Value::String(slug.clone()),
+        );
+    }
+
+    json_data
+}
+
+/// This function removes the configured fields supplied by the contributor from an entry,
+/// i.e. the `strip_fields` fields and the fields starting with any of the `strip_field_prefixes`.
+///
+/// ## Arguments
+/// - `output`: A reference to the `OutputConfig` struct that contains the stripped fields.
+/// - `json_data`: The JSON data of the entry.
+fn strip_fields(output: &OutputConfig, json_data: &mut serde_json::Value) {
+    let Some(object) = json_data.as_object_mut() else {
+        return;
+    };
+    object.retain(|key, _| {
+        !output.strip_fields.contains(key)
+            && !output
+                .strip_field_prefixes
+                .iter()
+                .any(|prefix| key.starts_with(prefix.as_str()))
+    });
+}
+
+/// This function sanitizes the configured string fields of an entry supplied by the contributor.
+///
+/// ## Arguments
+/// - `output`: A reference to the `OutputConfig` struct that contains the sanitized fields.
+/// - `entry_id`: The ID of the entry, used for logging.
+/// - `json_data`: The JSON data of the entry.
+fn sanitize_fields(output: &OutputConfig, entry_id: usize, json_data: &mut serde_json::Value) {
+    let Some(object) = json_data.as_object_mut() else {
+        return;
+    };
+    for field in &output.sanitize_fields {
+        if let Some(serde_json::Value::String(value)) = object.get_mut(field) {
+            let sanitized = sanitize::sanitize(value, output.sanitize_mode);
+            if sanitized != *value {
+                log_info!("Sanitized field {} of entry, ID: {}", field, entry_id);
+                *value = sanitized;
+            }
+        }
+    }
+}
+
+/// This function normalizes the avatar URL of an entry, if enabled:
+/// the default avatar is used when the avatar field is missing or empty,
+/// and a relative avatar URL is resolved against the origin of the `url` field of the entry.
+///
+/// ## Arguments
+/// - `output`: A reference to the `OutputConfig` struct that contains the avatar options.
+/// - `entry_id`: The ID of the entry, used for logging.
+/// - `json_data`: The JSON data of the entry.
+fn normalize_avatar(output: &OutputConfig, entry_id: usize, json_data: &mut serde_json::Value) {
+    let Some(object) = json_data.as_object_mut() else {
+        return;
+    };
+
+    // The avatar is missing if the field is absent, null or an empty string.
+    let avatar = match object.get(&output.avatar_field) {
+        None | Some(serde_json::Value::Null) => None,
+        Some(serde_json::Value::String(avatar)) if avatar.trim().is_empty() => None,
+        Some(serde_json::Value::String(avatar)) => Some(avatar.trim().to_string()),
+        // An avatar field of another type is kept as is.
+        Some(_) => return,
+    };
+
+    match avatar {
+        None => {
+            if let Some(default_avatar) = &output.default_avatar {
+                log_info!(
+                    "Using the default avatar for entry with missing avatar, ID: {}",
+                    entry_id
+                );
+                object.insert(
+                    output.avatar_field.clone(),
+                    serde_json::Value::String(default_avatar.clone()),
+                );
+            }
+        }
+        Some(avatar) if output.resolve_relative_avatar => {
+            // An avatar that can be parsed on its own is already absolute.
+            if reqwest::Url::parse(&avatar).is_ok() {
+                return;
+            }
+            let origin = object
+                .get("url")
+                .and_then(|url| url.as_str())
+                .and_then(|url| reqwest::Url::parse(url).ok())
+                .map(|url| url.origin())
+                .filter(|origin| origin.is_tuple())
+                .and_then(|origin| reqwest::Url::parse(&origin.ascii_serialization()).ok());
+            match origin.and_then(|origin| origin.join(&avatar).ok()) {
+                Some(resolved) => {
+                    log_info!(
+                        "Resolved relative avatar {} to {}, ID: {}",
+                        avatar,
+                        resolved,
+                        entry_id
+                    );
+                    object.insert(
+                        output.avatar_field.clone(),
+                        serde_json::Value::String(resolved.to_string()),
+                    );
+                }
+                None => log_warn!(
+                    "Cannot resolve relative avatar {} without a valid url field, ID: {}",
+                    avatar,
+                    entry_id
+                ),
+            }
+        }
+        _ => {}
+    }
+}
+
+/// This function assigns a unique slug to each entry, generated from the configured slug field.
+///
+/// Entries are processed in the order of their IDs, so that the slugs are deterministic across runs
+/// and a new entry never changes the slug of an existing one. When two entries have the same slug,
+/// a counter is appended to the slug of the later one (e.g. `blog-example-com-2`).
+/// Entries without the slug field fall back to `entry-<ID>`.
+///
+/// ## Arguments
+/// - `output`: A reference to the `OutputConfig` struct that contains the slug field.
+/// - `entries`: The entries to assign the slugs to.
+fn assign_slugs(output: &OutputConfig, entries: &mut [LinkEntry]) {
+    let mut order: Vec<usize> = (0..entries.len()).collect();
+    order.sort_by_key(|&index| entries[index].id);
+
+    let mut used_slugs: HashSet<String> = HashSet::new();
+    for index in order {
+        let entry = &mut entries[index];
+        let base_slug = entry
+            .json_data
+            .get(&output.slug_field)
+            .and_then(|value| value.as_str())
+            .map(|value| slug::slugify(&slug::slug_source(value)))
+            .filter(|slug| !slug.is_empty())
+            .unwrap_or_else(|| format!("entry-{}", entry.id));
+
+        let mut slug = base_slug.clone();
+        let mut counter = 2;
+        while used_slugs.contains(&slug) {
+            slug = format!("{}-{}", base_slug, counter);
+            counter += 1;
+        }
+        used_slugs.insert(slug.clone());
+        entry.slug = Some(slug);
+    }
+}
+
+/// This function converts a group and its list of entries into the group of the output file,
+/// including its child groups, if any.
+///
+/// ## Arguments
+/// - `config`: A reference to the `Config` struct that contains the sorting and output options.
+/// - `group`: A reference to the `GroupConfig` struct of the group.
+/// - `entries`: The entries of the group, in the sort order of the parent group (or of the generation
+///   for a top-level group), before the limit of the group is applied.
+/// - `parent_sort`: The resolved sort order of the parent group, if it or any of its ancestors overrides
+///   the sort order, which the sort order of the group inherits the fields it does not set from.
+/// - `repository_labels`: The labels of the repository, to find the GitHub label of the group.
+///
+/// ## Returns
+/// The `GroupOutput` struct representing the group in the output file.
+fn generate_group_json(
+    config: &Config,
+    group: &GroupConfig,
+    entries: &[LinkEntry],
+    parent_sort: Option<&GroupSort>,
+    repository_labels: &[Label],
+) -> GroupOutput {
+    let output = &config.output;
+    // Sort the entries again if the group overrides the sort order.
+    let group_sort = group.sort.as_ref().map(|sort| sort.inherit(parent_sort));
+    let mut sorted_entries: Vec<LinkEntry>;
+    let entries = match &group_sort {
+        Some(group_sort) => {
+            sorted_entries = entries.to_vec();
+            sort_entries(&config.generation, Some(group_sort), &mut sorted_entries);
+            sorted_entries.as_slice()
+        }
+        None => entries,
+    };
+    let group_sort = group_sort.as_ref().or(parent_sort);
+
+    // Find the GitHub label of the group, to fall back to its name and description: the label of the repository,
+    // so that an empty group is described too, or the label of an entry if the labels of the repository are unknown.
+    let github_label = group.label.iter().find_map(|label| {
+        repository_labels
+            .iter()
+            .find(|existing| existing.name == *label)
+            .or_else(|| entries.iter().find_map(|entry| entry.label(label)))
+    });
+    let group_name = group
+        .name
+        .clone()
+        .unwrap_or_else(|| group.primary_label().to_string());
+    let group_desc = group
+        .description
+        .clone()
+        .or_else(|| github_label.and_then(|label| label.description.clone()))
+        .unwrap_or_default();
+    let group_color = github_label
+        .and_then(|label| label.color.as_deref())
+        .filter(|color| output.include_group_color && !color.is_empty())
+        .map(|color| format!("#{}", color.trim_start_matches('#')));
+    // Only keep the first entries in the sort order if the group has a limit.
+    let (entry_ids, entry_values) = entries
+        .iter()
+        .take(group.max_entries.unwrap_or(usize::MAX))
+        .map(|entry| (entry.issue_id(), generate_entry_json(output, entry)))
+        .unzip();
+    // Create the output group.
+    let mut group_output = GroupOutput {
+        group: group.primary_label().to_string(),
+        group_name,
+        group_desc,
+        group_color,
+        entries: entry_values,
+        entry_ids,
+        children: None,
+    };
+
+    // Add the child groups, whose entries are the entries of this group that also carry the child label,
+    // taken before the limit of this group is applied.
+    if !group.children.is_empty() {
+        let children: Vec<GroupOutput> = group
+            .children
+            .iter()
+            .filter_map(|child| {
+                let child_entries: Vec<LinkEntry> = entries
+                    .iter()
+                    .filter(|entry| child.matches(|label| entry.has_label(label)))
+                    .cloned()
+                    .collect();
+                if child_entries.is_empty() && !output.include_empty_groups {
+                    return None;
+                }
+                Some(generate_group_json(
+                    config,
+                    child,
+                    &child_entries,
+                    group_sort,
+                    repository_labels,
+                ))
+            })
+            .collect();
+        group_output.children = Some(children);
+    }
+
+    group_output
+}
+
+/// This function returns the top-level groups in the order of the generated data: the groups listed
+/// in the group order file, if set, in the order of the file, followed by the other groups in the order
+/// of the configuration.
+///
+/// A warning is printed for every label of the file that is not the primary label of a group,
+/// and the order of the configuration is used if the file cannot be read.
+///
+/// ## Arguments
+/// - `config`: A reference to the `Config` struct that contains the groups and the path of the group order file.
+///
+/// ## Returns
+/// The top-level groups, in the order of the generated data.
+fn ordered_groups(config: &Config) -> Vec<&GroupConfig> {
+    let mut groups: Vec<&GroupConfig> = config.groups.iter().collect();
+    let Some(path) = &config.output.group_order_file else {
+        return groups;
+    };
+    let content = match std::fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) => {
+            log_warn!(
+                "Warning: Failed to Read Group Order File {}, Using the Configuration Order: {}",
+                path,
+                e
+            );
+            return groups;
+        }
+    };
+
+    let mut ordered: Vec<&GroupConfig> = Vec::new();
+    for label in content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+    {
+        match groups
+            .iter()
+            .position(|group| group.primary_label() == label)
+        {
+            Some(index) => ordered.push(groups.remove(index)),
+            None if ordered.iter().any(|group| group.primary_label() == label) => {
+                log_warn!(
+                    "Warning: The label \"{}\" is listed more than once in the group order file.",
+                    label
+                )
+            }
+            None => log_warn!(
+                "Warning: The label \"{}\" of the group order file is not the primary label of a group.",
+                label
+            ),
+        }
+    }
+    ordered.extend(groups);
+    ordered
+}
+
+/// This function converts the map between friend links groups and actual list of entries
+/// into the groups of the output file.
+///
+/// ## Arguments
+/// - `config`: A reference to the `Config` struct that contains the necessary information
+///   about the link groups and the output options.
+/// - `group_to_entry_map`: A reference to an `IndexMap` that maps link entries (as a vector)
+///   to their corresponding group primary labels.
+/// - `repository_labels`: The labels of the repository, to find the GitHub labels of the groups.
+///
+/// ## Returns
+/// The groups representing the generated data.
+fn generate_json(
+    config: &Config,
+    group_to_entry_map: &IndexMap<String, Vec<LinkEntry>>,
+    repository_labels: &[Label],
+) -> Vec<GroupOutput> {
+    let mut json_data: Vec<GroupOutput> = Vec::new();
+
+    for group in ordered_groups(config) {
+        // Get the entries for the current group.
+        let entries: &[LinkEntry] = group_to_entry_map
+            .get(group.primary_label())
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+        // Skip the group if it has no entries, unless empty groups should be included.
+        if entries.is_empty() && !config.output.include_empty_groups {
+            continue;
+        }
+        // Add the group JSON to the list.
+        json_data.push(generate_group_json(
+            config,
+            group,
+            entries,
+            None,
+            repository_labels,
+        ));
+    }
+
+    json_data
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_server::{self, StubResponse, StubServer};
+    use serde_json::json;
+    use std::sync::Once;
+
+    /// Parses a configuration for the repository `o/r`, with the given generation options and groups.
+    fn config(toml: &str) -> Config {
+        toml::from_str(&format!(
+            "[github]\nowner = \"o\"\nrepository = \"r\"\n\n{}",
+            toml
+        ))
+        .unwrap()
+    }
+
+    /// Parses a configuration with a single group and the given generation options.
+    fn config_with_generation(generation: &str) -> Config {
+        config(&format!(
+            "[generation]\n{}\n[[groups]]\nname = \"A\"\ndescription = \"\"\nlabel = \"a\"",
+            generation
+        ))
+    }
+
+    /// Creates the entry of an issue with its creation and update times, in seconds, and its labels.
+    fn entry(
+        id: usize,
+        created_at: i64,
+        updated_at: i64,
+        labels: &[&str],
+        json_data: serde_json::Value,
+    ) -> LinkEntry {
+        LinkEntry {
+            id,
+            number: Some(id),
+            html_url: None,
+            labels: labels
+                .iter()
+                .map(|name| github_api_responses::Label {
+                    id: 0,
+                    name: name.to_string(),
+                    description: None,
+                    color: None,
+                })
+                .collect(),
+            milestone: None,
+            json_data,
+            created_at: chrono::DateTime::from_timestamp(created_at, 0).unwrap(),
+            updated_at: chrono::DateTime::from_timestamp(updated_at, 0).unwrap(),
+            slug: None,
+            draft: false,
+        }
+    }
+
+    fn ids(entries: &[LinkEntry]) -> Vec<usize> {
+        entries.iter().map(|entry| entry.id).collect()
+    }
+
+    fn names(group: &GroupOutput) -> Vec<&str> {
+        group
+            .entries
+            .iter()
+            .map(|entry| entry["name"].as_str().unwrap())
+            .collect()
+    }
+
+    /// Points a configuration at the stub server.
+    ///
+    /// The GitHub token is set once for all the tests, before any of them reads it,
+    /// since setting an environment variable while another thread reads the environment is not safe.
+    fn stub_config(server: &StubServer, toml: &str) -> Config {
+        static SET_TOKEN: Once = Once::new();
+        SET_TOKEN.call_once(|| std::env::set_var("GITHUB_TOKEN", "test-token"));
+        let mut config = config(toml);
+        config.github.api_url = server.url.clone();
+        config
+    }
+
+    const GENERATION: &str = "[generation]\nlabel = \"active\"\nsort_by_updated_time = false\n\
+        [[groups]]\nname = \"A\"\ndescription = \"\"\nlabel = \"a\"";
+
+    #[tokio::test]
+    async fn fetches_all_the_pages_of_issues() {
+        let server = StubServer::start(vec![
+            StubResponse::ok(test_server::issues_page(1..101, &["active"])),
+            StubResponse::ok(test_server::issues_page(101..106, &["active"])),
+        ])
+        .await;
+        let config = stub_config(&server, GENERATION);
+        let issues = get_all_issues(&config, None, false).await.unwrap();
+        assert_eq!(issues.len(), 105);
+        assert_eq!(
+            server.requests(),
+            [
+                "/repos/o/r/issues?per_page=100&page=1",
+                "/repos/o/r/issues?per_page=100&page=2"
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn stops_after_a_page_that_is_not_full() {
+        let server = StubServer::start(vec![StubResponse::ok("[]")]).await;
+        let config = stub_config(&server, GENERATION);
+        assert!(get_all_issues(&config, Some("2024-01-01T00:00:00Z"), true)
+            .await
+            .unwrap()
+            .is_empty());
+        assert_eq!(
+            server.requests(),
+            ["/repos/o/r/issues?per_page=100&page=1&state=all&since=2024-01-01T00%3A00%3A00Z"]
+        );
+    }
+
+    #[tokio::test]
+    async fn generates_the_groups_from_the_served_issues() {
+        let issues = serde_json::Value::Array(vec![
+            test_server::issue_json(
+                1,
+                &["active", "friends"],
+                Some(&test_server::entry_body("A", "https://a.example.com")),
+            ),
+            test_server::issue_json(2, &["active", "friends"], Some("No data.")),
+            test_server::issue_json(
+                3,
+                &["friends"],
+                Some(&test_server::entry_body(
+                    "Inactive",
+                    "https://i.example.com",
+                )),
+            ),
+            test_server::issue_json(
+                4,
+                &["active", "friends"],
+                Some(&test_server::entry_body("B", "https://b.example.com")),
+            ),
+        ]);
+        let server = StubServer::start(vec![StubResponse::ok(issues.to_string())]).await;
+        let config = stub_config(
+            &server,
+            "[[groups]]\nlabel = \"friends\"\nname = \"Friends\"\ndescription = \"My friends\"",
+        );
+
+        let generation = run(&config).await.unwrap();
+        assert_eq!(generation.issues_fetched, 4);
+        assert_eq!(generation.rejections.len(), 1);
+        assert_eq!(generation.rejections[0].id, 2);
+        let groups: Vec<serde_json::Value> = generation
+            .groups
+            .iter()
+            .map(|group| serde_json::to_value(group).unwrap())
+            .collect();
+        assert_eq!(
+            groups,
+            [json!({
+                "group": "friends",
+                "groupName": "Friends",
+                "groupDesc": "My friends",
+                "entries": [
+                    { "name": "B", "url": "https://b.example.com" },
+                    { "name": "A", "url": "https://a.example.com" },
+                ],
+            })]
+        );
+    }
+
+    #[tokio::test]
+    async fn fails_on_a_malformed_or_forbidden_response() {
+        let server = StubServer::start(vec![StubResponse::ok(test_server::MALFORMED_BODY)]).await;
+        let config = stub_config(&server, "[[groups]]\nlabel = \"a\"");
+        assert!(matches!(run(&config).await, Err(FetchError::Parse(_))));
+
+        let server =
+            StubServer::start(vec![StubResponse::status(403, test_server::FORBIDDEN_BODY)]).await;
+        let config = stub_config(&server, "[[groups]]\nlabel = \"a\"");
+        assert!(matches!(
+            run(&config).await,
+            Err(FetchError::Credentials { .. })
+        ));
+    }
+
+    #[test]
+    fn matches_all_or_any_of_the_generation_labels() {
+        let entries = || {
+            vec![
+                entry(1, 10, 10, &["approved", "active"], json!({})),
+                entry(2, 20, 20, &["approved"], json!({})),
+                entry(3, 30, 30, &["active"], json!({})),
+                entry(4, 40, 40, &["other"], json!({})),
+            ]
+        };
+        let config = config_with_generation("label = [\"approved\", \"active\"]");
+        assert_eq!(
+            ids(&get_all_active_entries(&config.generation, entries())),
+            [1]
+        );
+
+        let config =
+            config_with_generation("label = [\"approved\", \"active\"]\nlabel_match = \"any\"");
+        assert_eq!(
+            ids(&get_all_active_entries(&config.generation, entries())),
+            [1, 2, 3]
+        );
+
+        // A single label is still accepted as a string.
+        let config = config_with_generation("label = \"approved\"");
+        assert_eq!(config.generation.label, ["approved"]);
+        assert_eq!(
+            ids(&get_all_active_entries(&config.generation, entries())),
+            [1, 2]
+        );
+    }
+
+    #[test]
+    fn excludes_the_entries_carrying_an_excluded_label() {
+        let config = config_with_generation("exclude_labels = [\"spam\", \"rejected\"]");
+        let entries = vec![
+            entry(1, 10, 10, &["active"], json!({})),
+            entry(2, 20, 20, &["active", "spam"], json!({})),
+            entry(3, 30, 30, &["active", "rejected"], json!({})),
+            // The exclusion is case-sensitive, like the labels of GitHub.
+            entry(4, 40, 40, &["active", "Spam"], json!({})),
+        ];
+        assert_eq!(
+            ids(&get_all_active_entries(&config.generation, entries)),
+            [1, 4]
+        );
+    }
+
+    #[test]
+    fn assigns_unique_slugs_in_the_order_of_the_ids() {
+        let config = config("[[groups]]\nlabel = \"a\"");
+        let mut entries = vec![
+            entry(
+                3,
+                10,
+                10,
+                &[],
+                json!({ "url": "https://blog.example.com/about" }),
+            ),
+            entry(
+                1,
+                20,
+                20,
+                &[],
+                json!({ "url": "https://www.blog.example.com" }),
+            ),
+            entry(2, 30, 30, &[], json!({ "url": "My Blog!" })),
+            entry(4, 40, 40, &[], json!({})),
+            entry(5, 50, 50, &[], json!({ "url": "https://blog.example.com" })),
+        ];
+        assign_slugs(&config.output, &mut entries);
+        let slugs: Vec<&str> = entries
+            .iter()
+            .map(|entry| entry.slug.as_deref().unwrap())
+            .collect();
+        assert_eq!(
+            slugs,
+            [
+                "blog-example-com-2",
+                "blog-example-com",
+                "my-blog",
+                "entry-4",
+                "blog-example-com-3"
+            ]
+        );
+    }
+
+    #[test]
+    fn nests_the_child_groups_on_two_levels() {
+        let config = config(
+            "[[groups]]\nlabel = \"tech\"\nname = \"Tech\"\n\
+             [[groups.children]]\nlabel = \"frontend\"\nname = \"Frontend\"\n\
+             [[groups.children.children]]\nlabel = \"react\"\nname = \"React\"\n\
+             [[groups]]\nlabel = \"life\"\nname = \"Life\"",
+        );
+        let entries = vec![
+            entry(1, 10, 10, &["tech"], json!({ "name": "a" })),
+            entry(2, 20, 20, &["tech", "frontend"], json!({ "name": "b" })),
+            entry(
+                3,
+                30,
+                30,
+                &["tech", "frontend", "react"],
+                json!({ "name": "c" }),
+            ),
+            // A child label without the label of the parent group is not enough.
+            entry(4, 40, 40, &["frontend", "react"], json!({ "name": "d" })),
+            entry(5, 50, 50, &["life"], json!({ "name": "e" })),
+        ];
+        let (map, _) = group_by_labels(&config, entries);
+        let groups: Vec<serde_json::Value> = generate_json(&config, &map, &[])
+            .iter()
+            .map(|group| serde_json::to_value(group).unwrap())
+            .collect();
+        assert_eq!(
+            groups,
+            [
+                json!({
+                    "group": "tech",
+                    "groupName": "Tech",
+                    "groupDesc": "",
+                    "entries": [{ "name": "a" }, { "name": "b" }, { "name": "c" }],
+                    "children": [{
+                        "group": "frontend",
+                        "groupName": "Frontend",
+                        "groupDesc": "",
+                        "entries": [{ "name": "b" }, { "name": "c" }],
+                        "children": [{
+                            "group": "react",
+                            "groupName": "React",
+                            "groupDesc": "",
+                            "entries": [{ "name": "c" }],
+                        }],
+                    }],
+                }),
+                // A group without children keeps the flat layout.
+                json!({
+                    "group": "life",
+                    "groupName": "Life",
+                    "groupDesc": "",
+                    "entries": [{ "name": "e" }],
+                }),
+            ]
+        );
+    }
+
+    fn issues(issues: Vec<serde_json::Value>) -> Vec<github_api_responses::Issue> {
+        serde_json::from_value(serde_json::Value::Array(issues)).unwrap()
+    }
+
+    fn reasons(rejections: &[Rejection]) -> Vec<(usize, &str)> {
+        rejections
+            .iter()
+            .map(|rejection| (rejection.id, rejection.reason))
+            .collect()
+    }
+
+    #[test]
+    fn rejects_the_issues_without_a_body() {
+        let config = config_with_generation("");
+        let issues = issues(vec![
+            test_server::issue_json(1, &["active"], None),
+            test_server::issue_json(2, &["active"], Some(" \n")),
+            test_server::issue_json(
+                3,
+                &["active"],
+                Some(&test_server::entry_body("A", "https://a.example.com")),
+            ),
+        ]);
+        let mut rejections = Vec::new();
+        let entries =
+            get_all_valid_issues(&config.generation, issues, &HashMap::new(), &mut rejections);
+        assert_eq!(ids(&entries), [3]);
+        assert_eq!(reasons(&rejections), [(1, "empty_body"), (2, "empty_body")]);
+    }
+
+    #[test]
+    fn rejects_the_issues_with_a_body_over_the_limit() {
+        let mut config = config_with_generation("");
+        let body = test_server::entry_body("A", "https://a.example.com");
+        config.generation.max_body_length = Some(body.len());
+        let oversized = format!("{}\n{}", body, "spam ".repeat(1000));
+        let issues = issues(vec![
+            test_server::issue_json(1, &["active"], Some(&oversized)),
+            test_server::issue_json(2, &["active"], Some(&body)),
+        ]);
+        let mut rejections = Vec::new();
+        let entries =
+            get_all_valid_issues(&config.generation, issues, &HashMap::new(), &mut rejections);
+        assert_eq!(ids(&entries), [2]);
+        assert_eq!(reasons(&rejections), [(1, "body_too_large")]);
+        assert_eq!(
+            rejections[0].message,
+            ExtractError::BodyTooLarge {
+                length: oversized.len(),
+                max_length: body.len(),
+            }
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn sorts_by_creation_time_newest_first_by_default() {
+        let config = config("[[groups]]\nlabel = \"a\"");
+        let mut entries = vec![
+            entry(1, 10, 40, &[], json!({})),
+            entry(2, 30, 30, &[], json!({})),
+            entry(3, 20, 50, &[], json!({})),
+        ];
+        sort_entries(&config.generation, None, &mut entries);
+        assert_eq!(ids(&entries), [2, 3, 1]);
+
+        let config = config.generation;
+        let sort = GroupSort {
+            sort_by: None,
+            sort_field: None,
+            sort_by_updated_time: Some(true),
+            descending: Some(false),
+        };
+        sort_entries(&config, Some(&sort), &mut entries);
+        assert_eq!(ids(&entries), [2, 1, 3]);
+    }
+
+    #[test]
+    fn sorts_by_a_field_with_the_entries_missing_it_last() {
+        let config = config_with_generation(
+            "sort_by = \"by_field\"\nsort_field = \"name\"\ndescending = false",
+        );
+        let mut entries = vec![
+            entry(1, 10, 10, &[], json!({ "name": "bravo" })),
+            entry(2, 20, 20, &[], json!({})),
+            entry(3, 30, 30, &[], json!({ "name": "  Alpha" })),
+            entry(4, 40, 40, &[], json!({ "name": "" })),
+            entry(5, 50, 50, &[], json!({ "name": "Écho" })),
+            entry(6, 60, 60, &[], json!({ "name": "charlie" })),
+        ];
+        sort_entries(&config.generation, None, &mut entries);
+        // The entries missing the field keep their time order.
+        assert_eq!(ids(&entries), [3, 1, 6, 5, 2, 4]);
+
+        let mut generation = config.generation;
+        generation.descending = true;
+        sort_entries(&generation, None, &mut entries);
+        assert_eq!(ids(&entries), [5, 6, 1, 3, 4, 2]);
+    }
+
+    #[test]
+    fn sorts_by_a_field_ignoring_the_diacritics() {
+        let config = config_with_generation("sort_by = \"by_field\"\ndescending = false");
+        let mut entries = vec![
+            entry(1, 10, 10, &[], json!({ "name": "Zulu" })),
+            entry(2, 20, 20, &[], json!({ "name": "Écho" })),
+            entry(3, 30, 30, &[], json!({ "name": "Ångström" })),
+            entry(4, 40, 40, &[], json!({ "name": "delta" })),
+            entry(5, 50, 50, &[], json!({ "name": "Łódź" })),
+            // The decomposed form (with a combining acute accent) is sorted like the composed one.
+            entry(6, 60, 60, &[], json!({ "name": "E\u{301}cho" })),
+        ];
+        sort_entries(&config.generation, None, &mut entries);
+        assert_eq!(ids(&entries), [3, 4, 2, 6, 5, 1]);
+    }
+
+    #[test]
+    fn sorts_groups_with_their_own_sort_order() {
+        let config = config(
+            "[[groups]]\nlabel = \"tools\"\nsort = { sort_by = \"by_field\", sort_field = \"name\", descending = false }\n\
+             [[groups]]\nlabel = \"blogs\"",
+        );
+        let entries = vec![
+            entry(1, 10, 10, &["tools", "blogs"], json!({ "name": "Charlie" })),
+            entry(2, 20, 20, &["tools", "blogs"], json!({ "name": "alpha" })),
+            entry(3, 30, 30, &["tools", "blogs"], json!({ "name": "Bravo" })),
+        ];
+        let mut map: IndexMap<String, Vec<LinkEntry>> = IndexMap::new();
+        let mut sorted = entries.clone();
+        sort_entries(&config.generation, None, &mut sorted);
+        map.insert("tools".to_string(), sorted.clone());
+        map.insert("blogs".to_string(), sorted);
+
+        let groups = generate_json(&config, &map, &[]);
+        assert_eq!(names(&groups[0]), ["alpha", "Bravo", "Charlie"]);
+        assert_eq!(names(&groups[1]), ["Bravo", "alpha", "Charlie"]);
+    }
+
+    #[test]
+    fn breaks_the_ties_between_duplicates_by_the_lowest_id() {
+        let entries = || {
+            vec![
+                entry(3, 10, 20, &[], json!({ "url": "https://a.example.com" })),
+                entry(1, 10, 20, &[], json!({ "url": "HTTPS://A.example.com/ " })),
+                entry(2, 5, 10, &[], json!({ "url": "https://b.example.com" })),
+                // Entries without the identity field are never duplicates.
+                entry(4, 10, 20, &[], json!({})),
+                entry(5, 10, 20, &[], json!({ "url": null })),
+            ]
+        };
+
+        let config =
+            config_with_generation("identity_field = \"url\"\non_duplicate = \"keep_latest\"");
+        let mut rejections = Vec::new();
+        let kept = remove_duplicate_entries(&config.generation, entries(), &mut rejections);
+        assert_eq!(ids(&kept), [1, 2, 4, 5]);
+        assert!(rejections.is_empty());
+
+        let config = config_with_generation("identity_field = \"url\"\non_duplicate = \"reject\"");
+        let kept = remove_duplicate_entries(&config.generation, entries(), &mut rejections);
+        assert_eq!(ids(&kept), [1, 2, 4, 5]);
+        assert_eq!(reasons(&rejections), [(3, "duplicate")]);
+
+        let config = config_with_generation("identity_field = \"url\"");
+        let mut rejections = Vec::new();
+        let kept = remove_duplicate_entries(&config.generation, entries(), &mut rejections);
+        assert_eq!(ids(&kept), [3, 1, 2, 4, 5]);
+    }
+
+    #[test]
+    fn compares_the_identity_values() {
+        assert_eq!(
+            identity_value(Some(&json!(" https://A.example.com/ "))),
+            Some("https://a.example.com".to_string())
+        );
+        assert_eq!(identity_value(Some(&json!(42))), Some("42".to_string()));
+        assert_eq!(identity_value(Some(&json!(null))), None);
+        assert_eq!(identity_value(None), None);
+    }
+
+    #[test]
+    fn child_groups_inherit_the_resolved_sort_order_of_their_parent() {
+        let config = config(
+            "[[groups]]\nlabel = \"parent\"\nsort = { sort_by = \"by_field\", sort_field = \"name\", descending = false }\n\
+             [[groups.children]]\nlabel = \"child\"\nsort = { descending = true }\n\
+             [[groups.children]]\nlabel = \"other\"",
+        );
+        let entries = vec![
+            entry(
+                1,
+                10,
+                10,
+                &["parent", "child", "other"],
+                json!({ "name": "b" }),
+            ),
+            entry(
+                2,
+                20,
+                20,
+                &["parent", "child", "other"],
+                json!({ "name": "a" }),
+            ),
+            entry(
+                3,
+                30,
+                30,
+                &["parent", "child", "other"],
+                json!({ "name": "c" }),
+            ),
+        ];
+        let group = generate_group_json(&config, &config.groups[0], &entries, None, &[]);
+        assert_eq!(names(&group), ["a", "b", "c"]);
+        let children = group.children.unwrap();
+        // The child only overrides the direction, it still sorts by name like its parent.
+        assert_eq!(names(&children[0]), ["c", "b", "a"]);
+        // A child without its own sort order keeps the order of its parent.
+        assert_eq!(names(&children[1]), ["a", "b", "c"]);
+    }
+
+    #[test]
+    fn describes_groups_from_the_labels_of_the_repository() {
+        let config = config(
+            "[output]\ninclude_empty_groups = true\ninclude_group_color = true\n\
+             [[groups]]\nlabel = \"friends\"\n[[groups]]\nlabel = \"empty\"",
+        );
+        let repository_labels = [
+            Label {
+                id: 1,
+                name: "friends".to_string(),
+                description: Some("My friends".to_string()),
+                color: Some("ff0000".to_string()),
+            },
+            Label {
+                id: 2,
+                name: "empty".to_string(),
+                description: Some("No one yet".to_string()),
+                color: Some("00ff00".to_string()),
+            },
+        ];
+        let mut map: IndexMap<String, Vec<LinkEntry>> = IndexMap::new();
+        map.insert(
+            "friends".to_string(),
+            vec![entry(1, 10, 10, &["friends"], json!({ "name": "a" }))],
+        );
+
+        let groups = generate_json(&config, &map, &repository_labels);
+        assert_eq!(groups[0].group_desc, "My friends");
+        assert_eq!(groups[0].group_color.as_deref(), Some("#ff0000"));
+        // The empty group is described by the label of the repository too.
+        assert_eq!(groups[1].group_desc, "No one yet");
+        assert_eq!(groups[1].group_color.as_deref(), Some("#00ff00"));
+
+        // Without the labels of the repository, the empty group has no description.
+        let groups = generate_json(&config, &map, &[]);
+        assert_eq!(groups[1].group_desc, "");
+    }
+
+    #[tokio::test]
+    async fn counts_the_labels_of_the_valid_entries() {
+        let config = config_with_generation("");
+        let body = test_server::entry_body("A", "https://a.example.com");
+        let mut pull_request = test_server::issue_json(4, &["active", "a"], Some(&body));
+        pull_request["pull_request"] = json!({});
+        let issues = issues(vec![
+            test_server::issue_json(1, &["active", "a"], Some(&body)),
+            // The inactive entries are also counted.
+            test_server::issue_json(2, &["a", "b"], Some(&body)),
+            test_server::issue_json(3, &["active", "b"], None),
+            pull_request,
+        ]);
+        let label_counts = count_labels(&config, issues).await;
+        assert_eq!(
+            label_counts,
+            BTreeMap::from([
+                ("a".to_string(), 2),
+                ("active".to_string(), 1),
+                ("b".to_string(), 1),
+            ])
+        );
+    }
+}
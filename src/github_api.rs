@@ -0,0 +1,452 @@
+// This file contains the helpers used to send requests to the GitHub API,
+// retrying them when GitHub is temporarily unavailable or the rate limit is exceeded.
+
+use crate::config::NetworkConfig;
+use crate::github_api_responses::ErrorResponse;
+use crate::logging::log_warn;
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use reqwest::{RequestBuilder, Response, StatusCode};
+use serde::de::DeserializeOwned;
+use std::fmt;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// The errors that can happen when sending a request to the GitHub API.
+#[derive(Debug)]
+pub enum FetchError {
+    /// The request could not be sent, or the response could not be read.
+    Request(reqwest::Error),
+    /// GitHub responded with an unsuccessful status code.
+    Status { status: StatusCode, body: String },
+    /// GitHub still responded with a server error (5xx) after all the attempts.
+    ServerError { status: StatusCode, attempts: u32 },
+    /// The rate limit is exceeded, and it is not reset soon enough to wait for it,
+    /// with the rate limit headers and the error message of the response.
+    RateLimited {
+        wait: Option<Duration>,
+        remaining: Option<u64>,
+        reset: Option<u64>,
+        message: String,
+    },
+    /// GitHub rejected the credentials (401), or they lack the permission to access the resource (403).
+    Credentials { status: StatusCode, message: String },
+    /// The response body could not be parsed.
+    Parse(serde_json::Error),
+    /// GitHub responded with an object (e.g. an error object) where a list was expected,
+    /// with the error message of the object, if any.
+    UnexpectedObject { message: Option<String> },
+    /// The file of the issues set with `github.issues_file` could not be read.
+    IssuesFile { path: String, error: std::io::Error },
+    /// The credentials could not be prepared before any request was sent (e.g. the `GITHUB_TOKEN`
+    /// environment variable is not set, or the private key of the GitHub App is invalid).
+    Auth(String),
+}
+
+impl fmt::Display for FetchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FetchError::Request(e) => write!(f, "Error Sending Request: {}", e),
+            FetchError::Status { status, body } => write!(f, "{}: {}", status, body),
+            FetchError::ServerError { status, attempts } => {
+                write!(f, "{} (after {} attempts)", status, attempts)
+            }
+            FetchError::RateLimited {
+                wait,
+                remaining,
+                reset,
+                message,
+            } => {
+                write!(f, "Rate limit exceeded")?;
+                if let Some(remaining) = remaining {
+                    write!(f, ", {} requests remaining", remaining)?;
+                }
+                if let Some(reset) = reset
+                    .and_then(|reset| chrono::DateTime::from_timestamp(reset as i64, 0))
+                {
+                    write!(f, ", reset at {}", reset.to_rfc3339())?;
+                }
+                if let Some(wait) = wait {
+                    write!(f, ", retry in {} seconds", wait.as_secs())?;
+                }
+                if !message.is_empty() {
+                    write!(f, ": {}", message)?;
+                }
+                Ok(())
+            }
+            FetchError::Credentials { status, message } => write!(
+                f,
+                "{}: {} (check that the GitHub token or GitHub App is valid and can access the repository)",
+                status, message
+            ),
+            FetchError::Parse(e) => write!(f, "Failed to Parse Response: {}", e),
+            FetchError::UnexpectedObject { message: Some(message) } => {
+                write!(f, "GitHub Responded with an Error Instead of a List: {}", message)
+            }
+            FetchError::UnexpectedObject { message: None } => write!(
+                f,
+                "GitHub Responded with a Single Object Instead of a List (check that `github.api_url`, `github.owner` and `github.repository` are correct)"
+            ),
+            FetchError::IssuesFile { path, error } => {
+                write!(f, "Failed to Read Issues File {}: {}", path, error)
+            }
+            FetchError::Auth(message) => write!(f, "Failed to Authenticate: {}", message),
+        }
+    }
+}
+
+/// This function builds the HTTP client of the requests sent to the GitHub API, with the timeout and the
+/// additional headers of the network configuration (e.g. for a corporate gateway). The headers set on every
+/// request (e.g. `Authorization`) win over the additional ones, which are only sent to the GitHub API,
+/// not to the checked links, the attachments or the webhook.
+///
+/// ## Arguments
+/// - `network`: A reference to the `NetworkConfig` struct that contains the timeout and the additional headers.
+///
+/// ## Returns
+/// The HTTP client, or a `FetchError` if it could not be built (e.g. the TLS backend cannot be initialized).
+pub(crate) fn github_client(network: &NetworkConfig) -> Result<reqwest::Client, FetchError> {
+    let mut headers = HeaderMap::new();
+    for (name, value) in &network.headers {
+        // The headers are validated with the configuration, an invalid one is only skipped here.
+        match (
+            HeaderName::from_bytes(name.as_bytes()),
+            HeaderValue::from_str(value),
+        ) {
+            (Ok(name), Ok(value)) => {
+                headers.insert(name, value);
+            }
+            _ => log_warn!("Warning: Skipping Invalid Header {}", name),
+        }
+    }
+    reqwest::Client::builder()
+        .timeout(Duration::from_secs(network.timeout_seconds))
+        .default_headers(headers)
+        .build()
+        .map_err(FetchError::Request)
+}
+
+/// Sends a request to the GitHub API, retrying it when it fails temporarily.
+///
+/// - Server errors (5xx) and timeouts are retried with an exponential backoff and jitter,
+///   up to `network.max_attempts` attempts in total.
+/// - Rate limit errors (403 or 429 with no remaining requests, or with a `Retry-After` header)
+///   are retried once the rate limit is reset, as long as the wait is at most `network.max_rate_limit_wait_seconds`.
+///   Otherwise the error reports the rate limit headers and the error message of GitHub.
+/// - Other 401 and 403 errors are reported as credential errors, since retrying them cannot help.
+///
+/// ## Arguments
+/// - `network`: A reference to the `NetworkConfig` struct that contains the retry options.
+/// - `request`: The request to be sent, which must be clonable (i.e. not have a streaming body).
+///
+/// ## Returns
+/// The successful response, or a `FetchError` if the request failed permanently.
+pub(crate) async fn send_with_retry(
+    network: &NetworkConfig,
+    request: RequestBuilder,
+) -> Result<Response, FetchError> {
+    let max_attempts = network.max_attempts.max(1);
+    let mut attempt: u32 = 0;
+    loop {
+        attempt += 1;
+        let res = request
+            .try_clone()
+            .expect("Request Cannot be Retried")
+            .send()
+            .await;
+
+        let delay = match res {
+            Ok(res) if res.status().is_success() => return Ok(res),
+            Ok(res) if res.status().is_server_error() => {
+                if attempt >= max_attempts {
+                    return Err(FetchError::ServerError {
+                        status: res.status(),
+                        attempts: attempt,
+                    });
+                }
+                log_warn!("GitHub responded with {}, retrying.", res.status());
+                backoff_delay(network, attempt)
+            }
+            Ok(res) if is_rate_limited(&res) => {
+                let wait = rate_limit_wait(&res);
+                match wait {
+                    Some(wait)
+                        if wait <= Duration::from_secs(network.max_rate_limit_wait_seconds) =>
+                    {
+                        log_warn!(
+                            "Rate limit exceeded, retrying in {} seconds.",
+                            wait.as_secs()
+                        );
+                        wait
+                    }
+                    _ => {
+                        let remaining = header_value(&res, "x-ratelimit-remaining");
+                        let reset = header_value(&res, "x-ratelimit-reset");
+                        let body = res.text().await.unwrap_or_default();
+                        return Err(FetchError::RateLimited {
+                            wait,
+                            remaining,
+                            reset,
+                            message: error_message(&body),
+                        });
+                    }
+                }
+            }
+            Ok(res) => {
+                let status = res.status();
+                let remaining = header_value(&res, "x-ratelimit-remaining");
+                let reset = header_value(&res, "x-ratelimit-reset");
+                let body = res.text().await.unwrap_or_default();
+                let message = error_message(&body);
+                // A secondary rate limit is only reported by the error message.
+                if (status == StatusCode::FORBIDDEN || status == StatusCode::TOO_MANY_REQUESTS)
+                    && message.to_lowercase().contains("rate limit")
+                {
+                    return Err(FetchError::RateLimited {
+                        wait: None,
+                        remaining,
+                        reset,
+                        message,
+                    });
+                }
+                if status == StatusCode::UNAUTHORIZED || status == StatusCode::FORBIDDEN {
+                    return Err(FetchError::Credentials { status, message });
+                }
+                return Err(FetchError::Status { status, body });
+            }
+            Err(e) if e.is_timeout() && attempt < max_attempts => {
+                log_warn!("Request timed out, retrying.");
+                backoff_delay(network, attempt)
+            }
+            Err(e) => return Err(FetchError::Request(e)),
+        };
+
+        tokio::time::sleep(delay).await;
+    }
+}
+
+/// Returns whether the response is a rate limit error.
+///
+/// See: https://docs.github.com/en/rest/using-the-rest-api/rate-limits-for-the-rest-api#exceeding-the-rate-limit
+fn is_rate_limited(res: &Response) -> bool {
+    let status = res.status();
+    if status != StatusCode::FORBIDDEN && status != StatusCode::TOO_MANY_REQUESTS {
+        return false;
+    }
+    res.headers().contains_key("retry-after")
+        || header_value(res, "x-ratelimit-remaining") == Some(0)
+}
+
+/// Returns how long to wait before the rate limit is reset, based on the `Retry-After`
+/// or `X-RateLimit-Reset` headers of the response, if any of them is present.
+fn rate_limit_wait(res: &Response) -> Option<Duration> {
+    if let Some(seconds) = header_value(res, "retry-after") {
+        return Some(Duration::from_secs(seconds));
+    }
+    let reset = header_value(res, "x-ratelimit-reset")?;
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).ok()?.as_secs();
+    Some(Duration::from_secs(reset.saturating_sub(now) + 1))
+}
+
+/// This function parses the body of a successful response of a GitHub API endpoint returning a list.
+///
+/// A body that is a JSON object instead of an array (e.g. an error object returned by a proxy,
+/// or a single object returned by another endpoint) is reported with its error message, if any,
+/// instead of an opaque parse error.
+///
+/// ## Arguments
+/// - `body`: The response body.
+///
+/// ## Returns
+/// The parsed list, or a `FetchError` if the body is not a list of the expected items.
+pub(crate) fn parse_list<T: DeserializeOwned>(body: &str) -> Result<Vec<T>, FetchError> {
+    if body.trim_start().starts_with('{') {
+        let message = serde_json::from_str::<ErrorResponse>(body)
+            .ok()
+            .map(|error| format_error(&error));
+        return Err(FetchError::UnexpectedObject { message });
+    }
+    serde_json::from_str(body).map_err(FetchError::Parse)
+}
+
+/// Returns the error message of a GitHub API error response body, followed by its documentation URL if any,
+/// or the body itself if it is not a GitHub error object.
+///
+/// See: https://docs.github.com/en/rest/using-the-rest-api/troubleshooting-the-rest-api
+fn error_message(body: &str) -> String {
+    match serde_json::from_str::<ErrorResponse>(body) {
+        Ok(error) => format_error(&error),
+        Err(_) => body.trim().to_string(),
+    }
+}
+
+/// Returns the error message of a GitHub API error object, followed by its documentation URL if any.
+fn format_error(error: &ErrorResponse) -> String {
+    match &error.documentation_url {
+        Some(url) => format!("{} (see {})", error.message, url),
+        None => error.message.clone(),
+    }
+}
+
+/// Returns the value of a numeric header of the response, if it is present and valid.
+fn header_value(res: &Response, name: &str) -> Option<u64> {
+    res.headers()
+        .get(name)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.trim().parse().ok())
+}
+
+/// Returns the delay before the next attempt, doubling the base delay after every attempt,
+/// with a random jitter so that concurrent runs do not retry at the same time.
+fn backoff_delay(network: &NetworkConfig, attempt: u32) -> Duration {
+    let delay = network
+        .retry_base_delay_ms
+        .saturating_mul(2u64.saturating_pow(attempt - 1));
+    // Use the sub-second part of the current time as a cheap source of randomness for the jitter.
+    let random = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.subsec_nanos() as u64);
+    let jitter = random % (delay / 2 + 1);
+    Duration::from_millis(delay / 2 + jitter)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::github_api_responses::Issue;
+    use crate::test_server::{self, StubResponse, StubServer};
+
+    /// Returns the network options of the tests, retrying without waiting.
+    fn network() -> NetworkConfig {
+        NetworkConfig {
+            retry_base_delay_ms: 1,
+            ..NetworkConfig::default()
+        }
+    }
+
+    async fn send(server: &StubServer) -> Result<Response, FetchError> {
+        let client = reqwest::Client::new();
+        send_with_retry(
+            &network(),
+            client.get(format!("{}/repos/o/r/issues", server.url)),
+        )
+        .await
+    }
+
+    #[tokio::test]
+    async fn returns_the_successful_response() {
+        let server = StubServer::start(vec![StubResponse::ok("[]")]).await;
+        let res = send(&server).await.unwrap();
+        assert_eq!(res.text().await.unwrap(), "[]");
+        assert_eq!(server.requests(), ["/repos/o/r/issues"]);
+    }
+
+    #[tokio::test]
+    async fn reports_a_forbidden_response_as_a_credentials_error() {
+        let server =
+            StubServer::start(vec![StubResponse::status(403, test_server::FORBIDDEN_BODY)]).await;
+        match send(&server).await {
+            Err(FetchError::Credentials { status, message }) => {
+                assert_eq!(status, StatusCode::FORBIDDEN);
+                assert_eq!(
+                    message,
+                    "Resource not accessible by personal access token (see https://docs.github.com/rest)"
+                );
+            }
+            other => panic!("Unexpected result: {:?}", other.map(|res| res.status())),
+        }
+        // A credentials error is never retried.
+        assert_eq!(server.requests().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn retries_a_rate_limited_response_once_reset() {
+        let server = StubServer::start(vec![
+            StubResponse::status(403, test_server::RATE_LIMITED_BODY)
+                .header("x-ratelimit-remaining", "0")
+                .header("retry-after", "0"),
+            StubResponse::ok("[]"),
+        ])
+        .await;
+        assert!(send(&server).await.is_ok());
+        assert_eq!(server.requests().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn reports_a_rate_limit_reset_too_late() {
+        let server = StubServer::start(vec![StubResponse::status(
+            403,
+            test_server::RATE_LIMITED_BODY,
+        )
+        .header("x-ratelimit-remaining", "0")
+        .header("x-ratelimit-reset", "99999999999")])
+        .await;
+        match send(&server).await {
+            Err(FetchError::RateLimited {
+                remaining, message, ..
+            }) => {
+                assert_eq!(remaining, Some(0));
+                assert_eq!(message, "API rate limit exceeded for user ID 1.");
+            }
+            other => panic!("Unexpected result: {:?}", other.map(|res| res.status())),
+        }
+        assert_eq!(server.requests().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn retries_a_server_error() {
+        let server = StubServer::start(vec![
+            StubResponse::status(503, ""),
+            StubResponse::status(503, ""),
+            StubResponse::ok("[]"),
+        ])
+        .await;
+        let res = send(&server).await.unwrap();
+        assert_eq!(res.text().await.unwrap(), "[]");
+        assert_eq!(server.requests().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn gives_up_after_the_maximum_attempts_of_server_errors() {
+        let server = StubServer::start(vec![StubResponse::status(502, "")]).await;
+        match send(&server).await {
+            Err(FetchError::ServerError { status, attempts }) => {
+                assert_eq!(status, StatusCode::BAD_GATEWAY);
+                assert_eq!(attempts, 3);
+            }
+            other => panic!("Unexpected result: {:?}", other.map(|res| res.status())),
+        }
+        assert_eq!(server.requests().len(), 3);
+    }
+
+    #[test]
+    fn parses_a_list() {
+        let body = test_server::issues_page(1..3, &["active"]);
+        let issues: Vec<Issue> = parse_list(&body).unwrap();
+        assert_eq!(
+            issues.iter().map(|issue| issue.id).collect::<Vec<_>>(),
+            [1, 2]
+        );
+    }
+
+    #[test]
+    fn rejects_a_malformed_body() {
+        assert!(matches!(
+            parse_list::<Issue>(test_server::MALFORMED_BODY),
+            Err(FetchError::Parse(_))
+        ));
+    }
+
+    #[test]
+    fn reports_an_object_instead_of_a_list() {
+        match parse_list::<Issue>(test_server::FORBIDDEN_BODY) {
+            Err(FetchError::UnexpectedObject { message }) => assert_eq!(
+                message.as_deref(),
+                Some("Resource not accessible by personal access token (see https://docs.github.com/rest)")
+            ),
+            other => panic!("Unexpected result: {:?}", other.map(|issues| issues.len())),
+        }
+        assert!(matches!(
+            parse_list::<Issue>(r#"{"id": 1}"#),
+            Err(FetchError::UnexpectedObject { message: None })
+        ));
+    }
+}
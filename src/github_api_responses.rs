@@ -1,61 +1,168 @@
 // This file contains the data structures used to deserialize the JSON responses from the GitHub API.
 
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 /// The structure of an individual issue in the response.
+/// It can also be serialized to be kept in the issue cache of the incremental generation.
 ///
 /// Note that this struct only contains the fields needed for the script to work,
 /// and not all fields in the response data.
 ///
 /// See: https://docs.github.com/en/rest/issues/issues?apiVersion=2022-11-28#list-repository-issues
-#[derive(Deserialize, Clone, Debug)]
-pub(crate) struct Issue {
-    pub(crate) id: usize,
-    pub(crate) url: String,
-    pub(crate) number: usize,
-    pub(crate) state: String,
-    pub(crate) title: String,
-    pub(crate) body: String,
-    pub(crate) labels: Vec<Label>,
-    pub(crate) closed_at: Option<String>,
-    pub(crate) created_at: String,
-    pub(crate) updated_at: String,
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct Issue {
+    pub id: usize,
+    pub url: String,
+    /// The URL of the issue on GitHub, which may be missing from the issues cached by older versions.
+    #[serde(default)]
+    pub html_url: Option<String>,
+    pub number: usize,
+    pub state: String,
+    pub title: String,
+    /// The user who opened the issue, which may be missing from the issues cached by older versions.
+    #[serde(default)]
+    pub user: Option<User>,
+    /// The body of the issue, which is `null` for issues created without a description.
+    pub body: Option<String>,
+    pub labels: Vec<Label>,
+    /// The milestone of the issue, if any.
+    #[serde(default)]
+    pub milestone: Option<Milestone>,
+    pub closed_at: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+    /// The pull request information, only present if the issue is actually a pull request,
+    /// since the issues endpoint also returns pull requests.
+    #[serde(default)]
+    pub pull_request: Option<serde_json::Value>,
 }
 
 impl Issue {
     /// Returns the closed_at date of the issue as a DateTime object.
     /// If the issue is not closed, it returns None.
-    pub(crate) fn closed_at(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+    pub fn closed_at(&self) -> Option<chrono::DateTime<chrono::Utc>> {
         self.closed_at
             .as_ref()
             .and_then(|date_str| chrono::DateTime::parse_from_rfc3339(date_str).ok())
             .map(|dt| dt.with_timezone(&chrono::Utc))
     }
 
-    /// Returns the created_at date of the issue as a DateTime object.
-    pub(crate) fn created_at(&self) -> chrono::DateTime<chrono::Utc> {
+    /// Returns the created_at date of the issue as a DateTime object,
+    /// or the parsing error if the date is malformed.
+    pub fn created_at(&self) -> Result<chrono::DateTime<chrono::Utc>, chrono::ParseError> {
         chrono::DateTime::parse_from_rfc3339(&self.created_at)
-            .expect("Failed to Parse created_at Date")
-            .with_timezone(&chrono::Utc)
+            .map(|dt| dt.with_timezone(&chrono::Utc))
     }
 
-    /// Returns the updated_at date of the issue as a DateTime object.
-    pub(crate) fn updated_at(&self) -> chrono::DateTime<chrono::Utc> {
+    /// Returns the updated_at date of the issue as a DateTime object,
+    /// or the parsing error if the date is malformed.
+    pub fn updated_at(&self) -> Result<chrono::DateTime<chrono::Utc>, chrono::ParseError> {
         chrono::DateTime::parse_from_rfc3339(&self.updated_at)
-            .expect("Failed to Parse updated_at Date")
-            .with_timezone(&chrono::Utc)
+            .map(|dt| dt.with_timezone(&chrono::Utc))
     }
 }
 
+/// The structure of the user who opened the issue.
+///
+/// Note that this struct only contains the fields needed for the script to work,
+/// and not all fields in the response data.
+///
+/// See: https://docs.github.com/en/rest/issues/issues?apiVersion=2022-11-28#list-repository-issues
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct User {
+    pub login: String,
+}
+
 /// The structure of a label in the issue.
 ///
 /// Note that this struct only contains the fields needed for the script to work,
 /// and not all fields in the response data.
 ///
 /// See: https://docs.github.com/en/rest/issues/issues?apiVersion=2022-11-28#list-repository-issues
-#[derive(Deserialize, Clone, Debug)]
-pub(crate) struct Label {
-    pub(crate) id: usize,
-    pub(crate) name: String,
-    pub(crate) description: String,
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct Label {
+    pub id: usize,
+    pub name: String,
+    pub description: Option<String>,
+    /// The color of the label, as a hexadecimal RGB value without the leading `#` (e.g. `ff0000`),
+    /// which may be missing from the issues cached by older versions.
+    #[serde(default)]
+    pub color: Option<String>,
+}
+
+/// The structure of the milestone of an issue.
+///
+/// Note that this struct only contains the fields needed for the script to work,
+/// and not all fields in the response data.
+///
+/// See: https://docs.github.com/en/rest/issues/milestones?apiVersion=2022-11-28#get-a-milestone
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct Milestone {
+    pub number: usize,
+    pub title: String,
+    pub description: Option<String>,
+    /// The due date of the milestone, as an ISO 8601 timestamp, if any.
+    pub due_on: Option<String>,
+}
+
+/// The structure of an event of the timeline of an issue.
+///
+/// Note that this struct only contains the fields needed for the script to work,
+/// and not all fields in the response data.
+///
+/// See: https://docs.github.com/en/rest/issues/timeline?apiVersion=2022-11-28#list-timeline-events-for-an-issue
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct TimelineEvent {
+    /// The type of the event (e.g. `labeled`), which is missing from some events.
+    #[serde(default)]
+    pub event: Option<String>,
+    /// The time of the event, which is missing from some events (e.g. the commits).
+    #[serde(default)]
+    pub created_at: Option<String>,
+    /// The label added or removed by a `labeled` or `unlabeled` event.
+    #[serde(default)]
+    pub label: Option<TimelineLabel>,
+}
+
+/// The structure of the label of a timeline event, which only has a name and a color.
+///
+/// See: https://docs.github.com/en/rest/issues/timeline?apiVersion=2022-11-28#list-timeline-events-for-an-issue
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct TimelineLabel {
+    pub name: String,
+}
+
+/// The structure of a repository.
+///
+/// Note that this struct only contains the fields needed for the script to work,
+/// and not all fields in the response data.
+///
+/// See: https://docs.github.com/en/rest/repos/repos?apiVersion=2022-11-28#get-a-repository
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct Repository {
+    pub full_name: String,
+    #[serde(default)]
+    pub private: bool,
+    /// The permissions of the credentials on the repository, only present for the authenticated requests.
+    #[serde(default)]
+    pub permissions: Option<RepositoryPermissions>,
+}
+
+/// The structure of the permissions of the credentials on a repository.
+///
+/// See: https://docs.github.com/en/rest/repos/repos?apiVersion=2022-11-28#get-a-repository
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct RepositoryPermissions {
+    #[serde(default)]
+    pub pull: bool,
+}
+
+/// The structure of an error response of the GitHub API (e.g. `{"message": "Bad credentials", ...}`).
+///
+/// See: https://docs.github.com/en/rest/using-the-rest-api/troubleshooting-the-rest-api
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct ErrorResponse {
+    pub message: String,
+    #[serde(default)]
+    pub documentation_url: Option<String>,
 }
@@ -0,0 +1,180 @@
+// This file contains the authentication to the GitHub API, either with the `GITHUB_TOKEN` environment variable
+// or with an installation token of a GitHub App.
+
+use crate::config::{AuthMode, Config};
+use crate::github_api::{self, FetchError};
+use crate::logging::log_info;
+use reqwest::header::{ACCEPT, AUTHORIZATION, USER_AGENT};
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+
+/// The time before its expiry after which a cached installation token is renewed, in seconds.
+const TOKEN_RENEWAL_MARGIN_SECONDS: i64 = 5 * 60;
+/// The lifetime of the JSON Web Token signed with the private key of the GitHub App (at most 10 minutes), in seconds.
+const JWT_LIFETIME_SECONDS: i64 = 9 * 60;
+/// How much the issue time of the JSON Web Token is set in the past, to allow for clock drift, in seconds.
+const JWT_CLOCK_DRIFT_SECONDS: i64 = 60;
+
+/// The installation token cached between the runs (e.g. in watch mode), with its expiry time.
+static CACHED_TOKEN: Mutex<Option<(String, chrono::DateTime<chrono::Utc>)>> = Mutex::new(None);
+
+/// The claims of the JSON Web Token used to authenticate as a GitHub App.
+#[derive(Serialize)]
+struct JwtClaims {
+    /// The issue time, in seconds since the Unix epoch.
+    iat: i64,
+    /// The expiry time, in seconds since the Unix epoch.
+    exp: i64,
+    /// The ID of the GitHub App.
+    iss: String,
+}
+
+/// The response of the GitHub API to the request of the installation of a GitHub App on a repository.
+#[derive(Deserialize)]
+struct Installation {
+    id: u64,
+}
+
+/// The response of the GitHub API to the request of an installation token.
+#[derive(Deserialize)]
+struct InstallationToken {
+    token: String,
+    expires_at: String,
+}
+
+/// This function returns the token used to authenticate the requests to the GitHub API.
+///
+/// With the `app` authentication, an installation token scoped to the repository is minted
+/// from the private key of the GitHub App, and cached until it is about to expire.
+///
+/// ## Arguments
+/// - `config`: A reference to the `Config` struct that contains the GitHub configuration and network options.
+/// - `client`: The Reqwest client used to send the requests.
+///
+/// ## Returns
+/// The token, or a `FetchError` if the `GITHUB_TOKEN` environment variable is not set (or empty),
+/// or if the installation token could not be minted.
+pub(crate) async fn github_token(
+    config: &Config,
+    client: &reqwest::Client,
+) -> Result<String, FetchError> {
+    if config.github.auth == AuthMode::Token {
+        return std::env::var("GITHUB_TOKEN")
+            .ok()
+            .filter(|token| !token.trim().is_empty())
+            .ok_or_else(|| {
+                FetchError::Auth("The GITHUB_TOKEN Environment Variable Is Not Set".to_string())
+            });
+    }
+
+    // Use the cached installation token if it is not about to expire.
+    let now = chrono::Utc::now();
+    if let Some((token, expires_at)) = CACHED_TOKEN.lock().unwrap().as_ref() {
+        if (*expires_at - now).num_seconds() > TOKEN_RENEWAL_MARGIN_SECONDS {
+            return Ok(token.clone());
+        }
+    }
+
+    let installation_token = mint_installation_token(config, client).await?;
+    let expires_at = chrono::DateTime::parse_from_rfc3339(&installation_token.expires_at)
+        .map_err(|e| FetchError::Auth(format!("Invalid Expiry Time of GitHub App Token: {}", e)))?
+        .with_timezone(&chrono::Utc);
+    log_info!(
+        "Minted a GitHub App installation token, expiring at {}.",
+        expires_at
+    );
+    *CACHED_TOKEN.lock().unwrap() = Some((installation_token.token.clone(), expires_at));
+    Ok(installation_token.token)
+}
+
+/// Mints an installation token of the GitHub App, scoped to the repository.
+///
+/// See: https://docs.github.com/en/apps/creating-github-apps/authenticating-with-a-github-app/authenticating-as-a-github-app-installation
+async fn mint_installation_token(
+    config: &Config,
+    client: &reqwest::Client,
+) -> Result<InstallationToken, FetchError> {
+    let jwt = app_jwt(config)?;
+    let api_url = config.github.api_url.trim_end_matches('/');
+
+    // Look up the installation of the GitHub App on the repository, if not configured.
+    let installation_id = match config.github.installation_id {
+        Some(installation_id) => installation_id,
+        None => {
+            let request = client.get(format!(
+                "{}/repos/{}/{}/installation",
+                api_url, config.github.owner, config.github.repository
+            ));
+            let body = send(config, request, &jwt).await?;
+            serde_json::from_str::<Installation>(&body)
+                .map_err(FetchError::Parse)?
+                .id
+        }
+    };
+
+    // Exchange the JSON Web Token for an installation token.
+    let request = client
+        .post(format!(
+            "{}/app/installations/{}/access_tokens",
+            api_url, installation_id
+        ))
+        .body(serde_json::json!({ "repositories": [config.github.repository] }).to_string());
+    let body = send(config, request, &jwt).await?;
+    serde_json::from_str(&body).map_err(FetchError::Parse)
+}
+
+/// Signs the JSON Web Token used to authenticate as the GitHub App with its private key.
+fn app_jwt(config: &Config) -> Result<String, FetchError> {
+    let app_id = config
+        .github
+        .app_id
+        .ok_or_else(|| FetchError::Auth("`github.app_id` Is Not Set".to_string()))?;
+    let private_key_path = config
+        .github
+        .private_key_path
+        .as_deref()
+        .filter(|path| !path.trim().is_empty())
+        .ok_or_else(|| FetchError::Auth("`github.private_key_path` Is Not Set".to_string()))?;
+
+    let private_key = std::fs::read(private_key_path).map_err(|e| {
+        FetchError::Auth(format!(
+            "Failed to Read GitHub App Private Key {}: {}",
+            private_key_path, e
+        ))
+    })?;
+    let key = jsonwebtoken::EncodingKey::from_rsa_pem(&private_key)
+        .map_err(|e| FetchError::Auth(format!("Invalid GitHub App Private Key: {}", e)))?;
+
+    let now = chrono::Utc::now().timestamp();
+    let claims = JwtClaims {
+        iat: now - JWT_CLOCK_DRIFT_SECONDS,
+        exp: now + JWT_LIFETIME_SECONDS,
+        iss: app_id.to_string(),
+    };
+    jsonwebtoken::encode(
+        &jsonwebtoken::Header::new(jsonwebtoken::Algorithm::RS256),
+        &claims,
+        &key,
+    )
+    .map_err(|e| {
+        FetchError::Auth(format!(
+            "Failed to Sign JSON Web Token of GitHub App: {}",
+            e
+        ))
+    })
+}
+
+/// Sends a request authenticated as the GitHub App, and reads the response body.
+async fn send(
+    config: &Config,
+    request: reqwest::RequestBuilder,
+    jwt: &str,
+) -> Result<String, FetchError> {
+    let request = request
+        .header(USER_AGENT, config.network.user_agent())
+        .header(ACCEPT, "application/vnd.github+json")
+        .header(AUTHORIZATION, format!("Bearer {}", jwt))
+        .header("X-GitHub-Api-Version", "2022-11-28");
+    let res = github_api::send_with_retry(&config.network, request).await?;
+    res.text().await.map_err(FetchError::Request)
+}
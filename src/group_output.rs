@@ -0,0 +1,63 @@
+use crate::config::OutputKeys;
+use serde::Serialize;
+
+/// The structure of a group in the generated data, as written to the output files.
+///
+/// It is serialized with the default key names, use `to_json` to use the configured ones.
+#[derive(Serialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct GroupOutput {
+    /// The primary label of the group, which identifies the group.
+    pub group: String,
+    /// The name of the group, falling back to the name of the GitHub label.
+    pub group_name: String,
+    /// The description of the group, falling back to the description of the GitHub label.
+    pub group_desc: String,
+    /// The color of the GitHub label of the group (e.g. `#ff0000`), only set if enabled and known.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub group_color: Option<String>,
+    /// The JSON data of the entries of the group.
+    pub entries: Vec<serde_json::Value>,
+    /// The IDs of the GitHub issues of the entries, in the order of `entries` (`None` for the static entries),
+    /// identifying the entries across runs. They are not written to the output files.
+    #[serde(skip)]
+    pub entry_ids: Vec<Option<usize>>,
+    /// The child groups of the group, only set if the group has child groups in the configuration.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub children: Option<Vec<GroupOutput>>,
+}
+
+impl GroupOutput {
+    /// Converts the group (and its child groups) into the JSON object of the output files,
+    /// using the configured key names.
+    pub fn to_json(&self, keys: &OutputKeys) -> serde_json::Value {
+        let mut object = serde_json::Map::new();
+        object.insert(keys.group.clone(), self.group.clone().into());
+        object.insert(keys.group_name.clone(), self.group_name.clone().into());
+        object.insert(keys.group_desc.clone(), self.group_desc.clone().into());
+        if let Some(group_color) = &self.group_color {
+            object.insert(keys.group_color.clone(), group_color.clone().into());
+        }
+        object.insert(keys.entries.clone(), self.entries.clone().into());
+        if let Some(children) = &self.children {
+            object.insert(
+                keys.children.clone(),
+                children
+                    .iter()
+                    .map(|child| child.to_json(keys))
+                    .collect::<Vec<_>>()
+                    .into(),
+            );
+        }
+        serde_json::Value::Object(object)
+    }
+
+    /// Adds the IDs of the entries of the group, and recursively of its child groups, to a list,
+    /// in the order of the entries in the output files (the entries of a group before the ones of its child groups).
+    pub fn collect_entry_ids(&self, ids: &mut Vec<Option<usize>>) {
+        ids.extend_from_slice(&self.entry_ids);
+        for child in self.children.iter().flatten() {
+            child.collect_entry_ids(ids);
+        }
+    }
+}
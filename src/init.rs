@@ -0,0 +1,49 @@
+// This file contains the scaffolding of a starter `config.toml` configuration file, used by `--init`.
+
+use std::fs;
+use std::path::Path;
+
+/// The template of the starter configuration file, with the required fields and one example group.
+/// All the other fields are optional, see the README for the full list and their defaults.
+const CONFIG_TEMPLATE: &str = r#"# Configuration of the blog friend links data generator.
+# Only the required fields are listed here, see the README for all the optional fields and their defaults.
+#
+# The GitHub token is not stored in this file: it is read from the GITHUB_TOKEN environment variable
+# (which is set automatically when the script is run by the GitHub Action).
+
+[github]
+# The owner (usually your GitHub username) of the repository where the friend link issues are submitted.
+owner="THE_REPO_OWNER"
+# The name of the repository where the friend link issues are submitted.
+repository="THE_REPO_NAME"
+
+[generation]
+# The label (or list of labels) identifying the active issues, whose entries are added to the generated data.
+label="active"
+
+# Every group categorizes the links carrying its label.
+# The name and description can be omitted to use the ones of the GitHub label.
+[[groups]]
+name="Friends"
+description="My friends and their blogs"
+label="friend"
+"#;
+
+/// This function writes the starter configuration file.
+///
+/// ## Arguments
+/// - `path`: The path of the configuration file to write.
+/// - `force`: Whether to overwrite the file if it already exists.
+///
+/// ## Returns
+/// Nothing, or a message describing why the file could not be written.
+pub(crate) fn write_config_template(path: &Path, force: bool) -> Result<(), String> {
+    if path.exists() && !force {
+        return Err(format!(
+            "{} already exists, use --force to overwrite it.",
+            path.display()
+        ));
+    }
+    fs::write(path, CONFIG_TEMPLATE)
+        .map_err(|e| format!("Failed to Write {}: {}", path.display(), e))
+}
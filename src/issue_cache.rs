@@ -0,0 +1,76 @@
+// This file contains the cache of the issues used by the incremental generation,
+// persisted between the runs so that only the issues changed since the last run are fetched.
+
+use crate::github_api_responses::Issue;
+use crate::logging::{log_error, log_info, log_warn};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+/// The structure of the persisted cache of the open issues of the repository.
+#[derive(Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct IssueCache {
+    /// The most recent updated time of the cached issues, used as the `since` parameter of the next run.
+    pub(crate) last_updated_at: Option<String>,
+    /// The cached open issues, from the newest to the oldest.
+    pub(crate) issues: Vec<Issue>,
+}
+
+impl IssueCache {
+    /// Loads the cache from a file, returning `None` if the file does not exist or cannot be parsed.
+    pub(crate) fn load(path: &Path) -> Option<IssueCache> {
+        let content = fs::read_to_string(path).ok()?;
+        match serde_json::from_str(&content) {
+            Ok(cache) => Some(cache),
+            Err(e) => {
+                log_warn!("Ignoring the invalid issue cache {}: {}", path.display(), e);
+                None
+            }
+        }
+    }
+
+    /// Saves the cache to a file, creating its parent directory if needed.
+    /// A failure is only reported, since the next run can still fetch all the issues.
+    pub(crate) fn save(&self, path: &Path) {
+        let result = path
+            .parent()
+            .filter(|parent| !parent.as_os_str().is_empty())
+            .map_or(Ok(()), fs::create_dir_all)
+            .and_then(|_| fs::write(path, serde_json::to_string(self).unwrap()));
+        if let Err(e) = result {
+            log_error!("Failed to Save Issue Cache {}: {}", path.display(), e);
+        }
+    }
+
+    /// Merges the issues changed since the last run into the cache:
+    /// the closed issues are removed, and the other ones are added or replaced (by their ID).
+    pub(crate) fn merge(&mut self, changed: Vec<Issue>) {
+        // The most recent updated time seen, including the closed issues, ignoring the malformed ones.
+        let changed_updated_at = changed
+            .iter()
+            .filter_map(|issue| Some((issue.updated_at().ok()?, issue.updated_at.clone())))
+            .max_by_key(|(updated_at, _)| *updated_at);
+
+        for issue in changed {
+            self.issues.retain(|cached| cached.id != issue.id);
+            if issue.state == "closed" {
+                log_info!("Removing closed issue from the cache, ID: {}", issue.id);
+            } else {
+                self.issues.push(issue);
+            }
+        }
+        // Keep the order of the issues endpoint, from the newest to the oldest.
+        self.issues
+            .sort_by(|a, b| (b.created_at().ok().cmp(&a.created_at().ok())).then(b.id.cmp(&a.id)));
+
+        if let Some((updated_at, updated_at_string)) = changed_updated_at {
+            let is_newer = self.last_updated_at.as_deref().map_or(true, |last| {
+                chrono::DateTime::parse_from_rfc3339(last).map_or(true, |last| updated_at > last)
+            });
+            if is_newer {
+                self.last_updated_at = Some(updated_at_string);
+            }
+        }
+    }
+}
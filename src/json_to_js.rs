@@ -1,16 +1,39 @@
+use crate::config::LargeIntegerMode;
 use serde_json::Value;
 use regex::Regex;
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashSet};
 
-/// Convert a Vec of serde_json::Value to JavaScript object string.
-pub fn json_to_js_object(data: &Vec<Value>) -> String {
-    json_to_js_format(&Value::Array(data.clone()), 0)
+/// The largest integer that a JavaScript number represents exactly (`Number.MAX_SAFE_INTEGER`).
+const MAX_SAFE_INTEGER: u64 = (1 << 53) - 1;
+
+/// The options of the conversion to a JavaScript object string.
+pub struct JsFormat<'a> {
+    /// Whether to pretty-print the object with the `indent`, or to minify it on a single line.
+    pub pretty: bool,
+    /// The string written for one level of indentation when the object is pretty-printed.
+    pub indent: &'a str,
+    /// How the integers beyond `Number.MAX_SAFE_INTEGER` are written, by field name
+    /// (including the values nested in the field). The other fields are written as plain numbers.
+    pub large_integer_fields: &'a BTreeMap<String, LargeIntegerMode>,
+    /// The number of decimals of the floating-point numbers, if fixed. The integers are always written
+    /// as integers, and the floating-point numbers otherwise keep their shortest form (e.g. `1.0` or `1e21`).
+    pub float_decimals: Option<usize>,
+    /// Whether to escape the `</` sequences of the strings as `<\/`, so that the object can be inlined
+    /// in an HTML `<script>` element without a string closing it early (e.g. `</script>`).
+    pub escape_script_close: bool,
+}
+
+/// Convert a serde_json::Value to JavaScript object string.
+pub fn json_to_js_object(data: &Value, format: &JsFormat) -> String {
+    json_to_js_format(data, 0, format, None)
 }
 
-/// Recursively convert serde_json::Value to JavaScript format string.
-fn json_to_js_format(obj: &Value, indent_level: usize) -> String {
-    let indent = "  ".repeat(indent_level);
-    let next_indent = "  ".repeat(indent_level + 1);
+/// Recursively convert serde_json::Value to JavaScript format string,
+/// where `field` is the name of the innermost object field containing the value, if any.
+fn json_to_js_format(obj: &Value, indent_level: usize, format: &JsFormat, field: Option<&str>) -> String {
+    let pretty = format.pretty;
+    let indent = format.indent.repeat(indent_level);
+    let next_indent = format.indent.repeat(indent_level + 1);
     
     match obj {
         Value::Object(map) => {
@@ -18,18 +41,27 @@ fn json_to_js_format(obj: &Value, indent_level: usize) -> String {
                 return "{}".to_string();
             }
             
+            // With the `preserve_order` feature of serde_json, the keys are iterated in their original order.
             let items: Vec<String> = map.iter().map(|(key, value)| {
                 let js_key = if is_valid_js_identifier(key) {
                     key.clone()
                 } else {
-                    format!("\"{}\"", key)
+                    js_string(key, format)
                 };
                 
-                let js_value = json_to_js_format(value, indent_level + 1);
-                format!("{}{}: {}", next_indent, js_key, js_value)
+                let js_value = json_to_js_format(value, indent_level + 1, format, Some(key));
+                if pretty {
+                    format!("{}{}: {}", next_indent, js_key, js_value)
+                } else {
+                    format!("{}:{}", js_key, js_value)
+                }
             }).collect();
             
-            format!("{{\n{}\n{}}}", items.join(",\n"), indent)
+            if pretty {
+                format!("{{\n{}\n{}}}", items.join(",\n"), indent)
+            } else {
+                format!("{{{}}}", items.join(","))
+            }
         }
         
         Value::Array(arr) => {
@@ -38,22 +70,22 @@ fn json_to_js_format(obj: &Value, indent_level: usize) -> String {
             }
             
             let items: Vec<String> = arr.iter().map(|item| {
-                let js_item = json_to_js_format(item, indent_level + 1);
-                format!("{}{}", next_indent, js_item)
+                let js_item = json_to_js_format(item, indent_level + 1, format, field);
+                if pretty {
+                    format!("{}{}", next_indent, js_item)
+                } else {
+                    js_item
+                }
             }).collect();
             
-            format!("[\n{}\n{}]", items.join(",\n"), indent)
+            if pretty {
+                format!("[\n{}\n{}]", items.join(",\n"), indent)
+            } else {
+                format!("[{}]", items.join(","))
+            }
         }
         
-        Value::String(s) => {
-            let escaped = s
-                .replace("\\", "\\\\")
-                .replace("\"", "\\\"")
-                .replace("\n", "\\n")
-                .replace("\r", "\\r")
-                .replace("\t", "\\t");
-            format!("\"{}\"", escaped)
-        }
+        Value::String(s) => js_string(s, format),
         
         Value::Bool(b) => {
             if *b { "true".to_string() } else { "false".to_string() }
@@ -61,10 +93,59 @@ fn json_to_js_format(obj: &Value, indent_level: usize) -> String {
         
         Value::Null => "null".to_string(),
         
-        Value::Number(n) => n.to_string()
+        Value::Number(n) => {
+            // serde_json cannot hold NaN or infinite numbers (they are converted to `null`),
+            // but they are also written as `null` here in case one ever reaches the output.
+            if n.as_f64().is_some_and(|f| !f.is_finite()) {
+                return "null".to_string();
+            }
+            // serde_json keeps the integers and the floating-point numbers apart, so `1.0` stays a float.
+            if let Some(decimals) = format.float_decimals {
+                if let Some(f) = n.as_f64().filter(|_| n.is_f64()) {
+                    return format!("{:.*}", decimals, f);
+                }
+            }
+            let is_large = n.as_u64().is_some_and(|i| i > MAX_SAFE_INTEGER)
+                || n.as_i64().is_some_and(|i| i.unsigned_abs() > MAX_SAFE_INTEGER);
+            let mode = field.and_then(|field| format.large_integer_fields.get(field));
+            match mode {
+                Some(LargeIntegerMode::BigInt) if is_large => format!("{}n", n),
+                Some(LargeIntegerMode::String) if is_large => format!("\"{}\"", n),
+                _ => n.to_string(),
+            }
+        }
     }
 }
 
+/// Convert a string to a double-quoted JavaScript string literal.
+///
+/// Besides the quotes and backslashes, the control characters and the U+2028 and U+2029 line separators
+/// are escaped, since they are valid in JSON strings but may break the JavaScript code when it is inlined
+/// in a `<script>` element, as well as the `</` sequences if enabled.
+fn js_string(s: &str, format: &JsFormat) -> String {
+    let mut escaped = String::with_capacity(s.len() + 2);
+    escaped.push('"');
+    let mut chars = s.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '"' => escaped.push_str("\\\""),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            '<' if format.escape_script_close && chars.peek() == Some(&'/') => {
+                chars.next();
+                escaped.push_str("<\\/");
+            }
+            '\u{2028}' | '\u{2029}' => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c if c.is_control() => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
+
 /// Check if a string is a valid JavaScript identifier.
 fn is_valid_js_identifier(name: &str) -> bool {
     // JavaScript identifier regex pattern
@@ -81,3 +162,80 @@ fn is_valid_js_identifier(name: &str) -> bool {
     
     re.is_match(name) && !reserved_words.contains(name)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    static NO_FIELDS: BTreeMap<String, LargeIntegerMode> = BTreeMap::new();
+
+    /// Returns the default options of the conversion, pretty-printed with two spaces or minified.
+    fn format(pretty: bool) -> JsFormat<'static> {
+        JsFormat {
+            pretty,
+            indent: "  ",
+            large_integer_fields: &NO_FIELDS,
+            float_decimals: None,
+            escape_script_close: false,
+        }
+    }
+
+    #[test]
+    fn quotes_the_reserved_words_and_invalid_identifiers_in_both_modes() {
+        let data = json!({ "name": "A", "class": "b", "default": [1, 2], "my-key": {}, "$ok": null });
+        assert_eq!(
+            json_to_js_object(&data, &format(true)),
+            "{\n  name: \"A\",\n  \"class\": \"b\",\n  \"default\": [\n    1,\n    2\n  ],\n  \"my-key\": {},\n  $ok: null\n}"
+        );
+        assert_eq!(
+            json_to_js_object(&data, &format(false)),
+            "{name:\"A\",\"class\":\"b\",\"default\":[1,2],\"my-key\":{},$ok:null}"
+        );
+    }
+
+    #[test]
+    fn escapes_the_line_separators_and_the_closing_script_tags() {
+        let data = json!({ "description": "Line\u{2028}and paragraph\u{2029} \"</script>\"\n\u{7}" });
+        assert_eq!(
+            json_to_js_object(&data, &format(false)),
+            r#"{description:"Line\u2028and paragraph\u2029 \"</script>\"\n\u0007"}"#
+        );
+        let format = JsFormat { escape_script_close: true, ..format(false) };
+        assert_eq!(
+            json_to_js_object(&data, &format),
+            r#"{description:"Line\u2028and paragraph\u2029 \"<\/script>\"\n\u0007"}"#
+        );
+        // Only the `<` of a `</` sequence is escaped.
+        assert_eq!(json_to_js_object(&json!("a < b <</"), &format), r#""a < b <<\/""#);
+    }
+
+    #[test]
+    fn keeps_the_integers_and_the_floats_apart() {
+        let data = json!([1, 1.0, -2.5, 1e21]);
+        assert_eq!(json_to_js_object(&data, &format(false)), "[1,1.0,-2.5,1e21]");
+        let format = JsFormat { float_decimals: Some(2), ..format(false) };
+        assert_eq!(
+            json_to_js_object(&json!([1, 1.0, 4.5678, -2.5]), &format),
+            "[1,1.00,4.57,-2.50]"
+        );
+    }
+
+    #[test]
+    fn writes_the_large_integers_of_the_configured_fields() {
+        let data = json!({ "id": [9007199254740993u64, 42], "count": 9007199254740993u64, "small": -9007199254740993i64 });
+        assert_eq!(
+            json_to_js_object(&data, &format(false)),
+            "{id:[9007199254740993,42],count:9007199254740993,small:-9007199254740993}"
+        );
+        let fields = BTreeMap::from([
+            ("id".to_string(), LargeIntegerMode::BigInt),
+            ("small".to_string(), LargeIntegerMode::String),
+        ]);
+        let format = JsFormat { large_integer_fields: &fields, ..format(false) };
+        assert_eq!(
+            json_to_js_object(&data, &format),
+            "{id:[9007199254740993n,42],count:9007199254740993,small:\"-9007199254740993\"}"
+        );
+    }
+}
@@ -0,0 +1,147 @@
+// This file contains the conversion of the keys of the generated data to a naming convention.
+
+use crate::config::{KeyCase, OutputKeys};
+use crate::logging::log_warn;
+use serde_json::Value;
+
+/// This function recursively converts all the object keys of a JSON value (e.g. the envelope,
+/// the groups and the entries of the generated data) to a naming convention.
+///
+/// When two keys of the same object convert to the same key, the first one is kept and a warning is printed.
+///
+/// ## Arguments
+/// - `value`: The JSON value to convert.
+/// - `case`: The naming convention of the keys.
+///
+/// ## Returns
+/// The JSON value with the converted keys, in the same order.
+pub(crate) fn convert_keys(value: Value, case: KeyCase) -> Value {
+    match value {
+        Value::Object(object) => {
+            let mut converted = serde_json::Map::new();
+            for (key, value) in object {
+                let new_key = convert_key(&key, case);
+                if converted.contains_key(&new_key) {
+                    log_warn!(
+                        "Warning: The key \"{}\" converts to the already used key \"{}\", skipping it.",
+                        key,
+                        new_key
+                    );
+                    continue;
+                }
+                converted.insert(new_key, convert_keys(value, case));
+            }
+            Value::Object(converted)
+        }
+        Value::Array(values) => Value::Array(
+            values
+                .into_iter()
+                .map(|value| convert_keys(value, case))
+                .collect(),
+        ),
+        value => value,
+    }
+}
+
+/// Returns the key names of the group objects converted to a naming convention.
+pub(crate) fn convert_output_keys(keys: &OutputKeys, case: KeyCase) -> OutputKeys {
+    OutputKeys {
+        group: convert_key(&keys.group, case),
+        group_name: convert_key(&keys.group_name, case),
+        group_desc: convert_key(&keys.group_desc, case),
+        entries: convert_key(&keys.entries, case),
+        children: convert_key(&keys.children, case),
+        group_color: convert_key(&keys.group_color, case),
+    }
+}
+
+/// This function converts a single key to a naming convention, e.g. `issueURL` becomes `issue_url` in snake case.
+///
+/// A key already following the naming convention is kept as it is, as well as its leading underscores
+/// (e.g. `_comment`).
+///
+/// ## Arguments
+/// - `key`: The key to convert.
+/// - `case`: The naming convention of the key.
+///
+/// ## Returns
+/// The converted key.
+pub(crate) fn convert_key(key: &str, case: KeyCase) -> String {
+    let name = key.trim_start_matches('_');
+    let prefix = &key[..key.len() - name.len()];
+    let is_lower = |c: char| !c.is_uppercase();
+    let already_converted = match case {
+        KeyCase::Camel => {
+            name.chars().next().map_or(true, is_lower) && name.chars().all(|c| c.is_alphanumeric())
+        }
+        KeyCase::Snake => name
+            .chars()
+            .all(|c| c == '_' || c.is_alphanumeric() && is_lower(c)),
+        KeyCase::Kebab => name
+            .chars()
+            .all(|c| c == '-' || c.is_alphanumeric() && is_lower(c)),
+    };
+    if already_converted {
+        return key.to_string();
+    }
+
+    let words = split_words(name);
+    let converted = match case {
+        KeyCase::Camel => words
+            .iter()
+            .enumerate()
+            .map(|(index, word)| {
+                if index == 0 {
+                    word.to_lowercase()
+                } else {
+                    capitalize(word)
+                }
+            })
+            .collect(),
+        KeyCase::Snake => words.join("_").to_lowercase(),
+        KeyCase::Kebab => words.join("-").to_lowercase(),
+    };
+    format!("{}{}", prefix, converted)
+}
+
+/// Splits a key into its words, at the separators (`_`, `-`, spaces and dots) and at the case changes,
+/// keeping the acronyms together (e.g. `HTMLPage` is split into `HTML` and `Page`).
+fn split_words(key: &str) -> Vec<String> {
+    let chars: Vec<char> = key.chars().collect();
+    let mut words: Vec<String> = Vec::new();
+    let mut word = String::new();
+    for (index, &c) in chars.iter().enumerate() {
+        if matches!(c, '_' | '-' | ' ' | '.') {
+            if !word.is_empty() {
+                words.push(std::mem::take(&mut word));
+            }
+            continue;
+        }
+        if c.is_uppercase() && !word.is_empty() {
+            let previous = chars[index - 1];
+            let next_is_lower = chars.get(index + 1).is_some_and(|next| next.is_lowercase());
+            // A new word starts at an uppercase letter after a lowercase letter or a digit,
+            // or at the last letter of an acronym followed by a lowercase letter.
+            if !previous.is_uppercase() || next_is_lower {
+                words.push(std::mem::take(&mut word));
+            }
+        }
+        word.push(c);
+    }
+    if !word.is_empty() {
+        words.push(word);
+    }
+    words
+}
+
+/// Returns the word with its first letter in uppercase and the other ones in lowercase.
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first
+            .to_uppercase()
+            .chain(chars.flat_map(char::to_lowercase))
+            .collect(),
+        None => String::new(),
+    }
+}
@@ -0,0 +1,142 @@
+// This file contains the startup check that the configured labels exist on the repository.
+
+use crate::config::{Config, GroupConfig};
+use crate::github_api::{self, FetchError};
+use crate::github_api_responses::Label;
+use crate::github_auth;
+use crate::logging::log_warn;
+use reqwest::header::{ACCEPT, AUTHORIZATION, USER_AGENT};
+
+/// The number of labels requested per page from the GitHub API (the maximum allowed by the API).
+const LABELS_PER_PAGE: usize = 100;
+
+/// This function fetches the labels of the repository for the label check (see `check_labels`),
+/// if the check is enabled and the repository is reached, i.e. the issues are not read from a file.
+///
+/// It is called once at the start of a run, so that the labels are not fetched again for every generation.
+/// A warning is printed if the labels cannot be fetched, since the check never fails the run.
+///
+/// ## Arguments
+/// - `config`: A reference to the `Config` struct that contains the GitHub options.
+///
+/// ## Returns
+/// The labels of the repository, or `None` if the check is disabled or the labels could not be fetched.
+pub async fn fetch_labels(config: &Config) -> Option<Vec<Label>> {
+    if !config.github.check_labels || config.github.issues_file.is_some() {
+        return None;
+    }
+    match get_repository_labels(config).await {
+        Ok(labels) => Some(labels),
+        Err(e) => {
+            log_warn!(
+                "Warning: Failed to Fetch Repository Labels, Skipping Label Check: {}",
+                e
+            );
+            None
+        }
+    }
+}
+
+/// This function checks that the generation labels and the labels of all the groups (including the child groups)
+/// exist on the repository, and prints a warning for every label that does not, since the entries would
+/// silently never match it. The labels are matched case-sensitively, like the labels of the issues,
+/// and a label only differing by case is not reported when `generation.case_insensitive_labels` is enabled.
+///
+/// ## Arguments
+/// - `config`: A reference to the `Config` struct that contains the configured labels.
+/// - `repository_labels`: The labels of the repository (see `fetch_labels`).
+pub fn check_labels(config: &Config, repository_labels: &[Label]) {
+    let mut configured: Vec<(&str, String)> = config
+        .generation
+        .label
+        .iter()
+        .map(|label| (label.as_str(), "`generation.label`".to_string()))
+        .collect();
+    collect_group_labels(&config.groups, &mut configured);
+
+    for (label, used_by) in configured {
+        if repository_labels
+            .iter()
+            .any(|existing| existing.name == label)
+        {
+            continue;
+        }
+        // Suggest a label differing only by case, which is the most common mistake,
+        // unless the labels are matched ignoring case.
+        match repository_labels
+            .iter()
+            .find(|existing| existing.name.to_lowercase() == label.to_lowercase())
+        {
+            Some(_) if config.generation.case_insensitive_labels => {}
+            Some(existing) => log_warn!(
+                "Warning: The label \"{}\" of {} does not exist on the repository, did you mean \"{}\"? Enable `generation.case_insensitive_labels` to match the labels ignoring case.",
+                label, used_by, existing.name
+            ),
+            None => log_warn!(
+                "Warning: The label \"{}\" of {} does not exist on the repository.",
+                label, used_by
+            ),
+        }
+    }
+}
+
+/// Adds the labels of a list of groups, and recursively of their child groups,
+/// with a description of the group using them.
+fn collect_group_labels<'a>(groups: &'a [GroupConfig], labels: &mut Vec<(&'a str, String)>) {
+    for group in groups {
+        let used_by = format!(
+            "group \"{}\"",
+            group.name.as_deref().unwrap_or(group.primary_label())
+        );
+        for label in &group.label {
+            labels.push((label, used_by.clone()));
+        }
+        collect_group_labels(&group.children, labels);
+    }
+}
+
+/// This function retrieves all the labels of the repository, page by page.
+///
+/// See: https://docs.github.com/en/rest/issues/labels?apiVersion=2022-11-28#list-labels-for-a-repository
+///
+/// ## Arguments
+/// - `config`: A reference to a `Config` struct that contains the GitHub API URL, owner, repository name,
+///   and network options.
+///
+/// ## Returns
+/// The labels of the repository, or a `FetchError` if they could not be fetched.
+async fn get_repository_labels(config: &Config) -> Result<Vec<Label>, FetchError> {
+    let client = github_api::github_client(&config.network)?;
+    let github_token = github_auth::github_token(config, &client).await?;
+    let url = format!(
+        "{}/repos/{}/{}/labels",
+        config.github.api_url.trim_end_matches('/'),
+        config.github.owner,
+        config.github.repository
+    );
+
+    let mut labels: Vec<Label> = Vec::new();
+    let mut page = 1;
+    loop {
+        let request = client
+            .get(&url)
+            .query(&[("per_page", LABELS_PER_PAGE), ("page", page)])
+            .header(USER_AGENT, config.network.user_agent())
+            .header(ACCEPT, "application/vnd.github+json")
+            .header(AUTHORIZATION, format!("Bearer {}", github_token))
+            .header("X-GitHub-Api-Version", "2022-11-28");
+        let res = github_api::send_with_retry(&config.network, request).await?;
+        let body = res.text().await.map_err(FetchError::Request)?;
+        let page_labels: Vec<Label> = github_api::parse_list(&body)?;
+
+        // A page that is not full means that there are no more labels to fetch.
+        let page_len = page_labels.len();
+        labels.extend(page_labels);
+        if page_len < LABELS_PER_PAGE {
+            break;
+        }
+        page += 1;
+    }
+
+    Ok(labels)
+}
@@ -0,0 +1,46 @@
+//! The library API of the generator, used to embed the generation of the friend links data in another tool
+//! instead of running the script.
+//!
+//! The `src/script.ers` script is a thin wrapper over this library, which only adds the command line,
+//! the rendering of the output formats and the writing of the output files. A minimal usage of the library would be:
+//!
+//! ```no_run
+//! # async fn example() -> Result<(), blog_friend_links_data_generator::FetchError> {
+//! let config: blog_friend_links_data_generator::Config =
+//!     toml::from_str(&std::fs::read_to_string("config.toml").unwrap()).unwrap();
+//! let groups = blog_friend_links_data_generator::generate(&config).await?;
+//! # Ok(())
+//! # }
+//! ```
+
+mod attachment;
+mod computed;
+pub mod config;
+pub mod edit_check;
+mod extract;
+mod fetch_progress;
+pub mod generator;
+pub mod github_api;
+pub mod github_api_responses;
+mod github_auth;
+pub mod group_output;
+mod issue_cache;
+pub mod label_check;
+pub mod link_check;
+pub mod link_entry;
+pub mod logging;
+mod milestones;
+pub mod preflight;
+pub mod rejection;
+pub mod sanitize;
+mod slug;
+mod static_entries;
+#[cfg(test)]
+mod test_server;
+mod transform;
+
+pub use config::Config;
+pub use generator::{generate, run, Generation};
+pub use github_api::FetchError;
+pub use group_output::GroupOutput;
+pub use link_entry::LinkEntry;
@@ -0,0 +1,241 @@
+// This file contains the checking of the links of the entries, bounding the number of concurrent requests
+// globally and per host, so that a host owning many of the links is not hammered.
+
+use crate::config::LinkCheckConfig;
+use crate::link_entry::LinkEntry;
+use crate::logging::{log_error, log_warn};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+
+/// The result of the check of the link of an entry.
+#[derive(Serialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct LinkCheck {
+    /// The ID of the entry.
+    pub id: usize,
+    /// The checked link, empty if the entry does not have a valid link.
+    pub url: String,
+    /// The final HTTP status code of the link (after following the redirects), if a response was received.
+    pub status: Option<u16>,
+    /// The number of requests sent.
+    pub attempts: u32,
+    /// Whether the link is reachable, i.e. responded with a successful status code.
+    pub alive: bool,
+    /// The description of the error of the last attempt, if the link is not reachable.
+    pub error: Option<String>,
+    /// The `Content-Type` header of the response, only set for the checks of the avatars.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content_type: Option<String>,
+}
+
+/// This function checks the links of the entries, with at most `concurrency` requests at the same time,
+/// and at most `per_host_concurrency` requests to the same host (including its subdomains).
+/// A request failing with a timeout or a connection error is retried up to `max_attempts` attempts in total.
+///
+/// ## Arguments
+/// - `config`: A reference to the `LinkCheckConfig` struct that contains the link checking options.
+/// - `user_agent`: The `User-Agent` header of the requests.
+/// - `entries`: The entries whose links are checked.
+///
+/// ## Returns
+/// The results of the checks, in the order of the entries.
+pub(crate) async fn check_links(
+    config: &LinkCheckConfig,
+    user_agent: &str,
+    entries: &[LinkEntry],
+) -> Vec<LinkCheck> {
+    check_urls(config, user_agent, entries, &config.url_field, false).await
+}
+
+/// This function checks that the avatars of the entries are actual images, i.e. that their URL responds
+/// with a successful status code and an `image/*` `Content-Type` header, with the same limits as `check_links`.
+/// Only the entries whose `avatar_field` field is an absolute HTTP(S) URL are checked.
+///
+/// ## Arguments
+/// - `config`: A reference to the `LinkCheckConfig` struct that contains the link checking options.
+/// - `user_agent`: The `User-Agent` header of the requests.
+/// - `entries`: The entries whose avatars are checked.
+///
+/// ## Returns
+/// The results of the checks of the checked avatars, in the order of the entries,
+/// where `alive` tells whether the avatar is an image.
+pub(crate) async fn check_avatars(
+    config: &LinkCheckConfig,
+    user_agent: &str,
+    entries: &[LinkEntry],
+) -> Vec<LinkCheck> {
+    check_urls(config, user_agent, entries, &config.avatar_field, true).await
+}
+
+/// Checks the URLs of a field of the entries (see `check_links`), also checking that they are images
+/// if `image` is set, in which case the entries without a valid URL are skipped instead of reported.
+async fn check_urls(
+    config: &LinkCheckConfig,
+    user_agent: &str,
+    entries: &[LinkEntry],
+    field: &str,
+    image: bool,
+) -> Vec<LinkCheck> {
+    let client = match reqwest::Client::builder()
+        .timeout(Duration::from_secs(config.timeout_seconds))
+        .user_agent(user_agent)
+        .build()
+    {
+        Ok(client) => client,
+        Err(e) => {
+            log_warn!(
+                "Warning: Failed to Build HTTP Client, Skipping {} Check: {}",
+                if image { "Avatar" } else { "Link" },
+                e
+            );
+            return Vec::new();
+        }
+    };
+    let global_permits = Arc::new(Semaphore::new(config.concurrency.max(1)));
+    let mut host_permits: HashMap<String, Arc<Semaphore>> = HashMap::new();
+
+    let mut tasks: JoinSet<(usize, LinkCheck)> = JoinSet::new();
+    let mut results: Vec<Option<LinkCheck>> = vec![None; entries.len()];
+    let mut checked_urls: Vec<(usize, String)> = Vec::new();
+    for (index, entry) in entries.iter().enumerate() {
+        let url = entry.json_data[field]
+            .as_str()
+            .and_then(|url| reqwest::Url::parse(url).ok())
+            .filter(|url| matches!(url.scheme(), "http" | "https"));
+        let Some(url) = url else {
+            if !image {
+                results[index] = Some(LinkCheck {
+                    id: entry.id,
+                    url: String::new(),
+                    status: None,
+                    attempts: 0,
+                    alive: false,
+                    error: Some(format!("Missing or invalid `{}` field", field)),
+                    content_type: None,
+                });
+            }
+            continue;
+        };
+
+        checked_urls.push((index, url.to_string()));
+        let host_permit = host_permits
+            .entry(host_key(&url))
+            .or_insert_with(|| Arc::new(Semaphore::new(config.per_host_concurrency.max(1))))
+            .clone();
+        let global_permit = global_permits.clone();
+        let client = client.clone();
+        let max_attempts = config.max_attempts.max(1);
+        let retry_delay = Duration::from_millis(config.retry_delay_ms);
+        let id = entry.id;
+        tasks.spawn(async move {
+            // Wait for the host first, so that a busy host does not hold the global permits.
+            let _host_permit = host_permit.acquire_owned().await.unwrap();
+            let _global_permit = global_permit.acquire_owned().await.unwrap();
+            let check = check_link(&client, id, url, image, max_attempts, retry_delay).await;
+            (index, check)
+        });
+    }
+
+    while let Some(result) = tasks.join_next().await {
+        match result {
+            Ok((index, check)) => results[index] = Some(check),
+            Err(e) => log_error!("Link Check Task Failed: {}", e),
+        }
+    }
+    // The links whose task failed are reported as not checked, so that no entry is silently missing.
+    for (index, url) in checked_urls {
+        if results[index].is_none() {
+            results[index] = Some(LinkCheck {
+                id: entries[index].id,
+                url,
+                status: None,
+                attempts: 0,
+                alive: false,
+                error: Some("The check task failed".to_string()),
+                content_type: None,
+            });
+        }
+    }
+    results.into_iter().flatten().collect()
+}
+
+/// Checks a single link, retrying it when it fails with a timeout or a connection error.
+/// If `image` is set, the link must also respond with an `image/*` `Content-Type` header.
+async fn check_link(
+    client: &reqwest::Client,
+    id: usize,
+    url: reqwest::Url,
+    image: bool,
+    max_attempts: u32,
+    retry_delay: Duration,
+) -> LinkCheck {
+    let mut attempts = 0;
+    loop {
+        attempts += 1;
+        match client.get(url.clone()).send().await {
+            Ok(res) => {
+                let status = res.status();
+                // Only the headers are read, the body of the response is never downloaded.
+                let content_type = image.then(|| {
+                    res.headers()
+                        .get(reqwest::header::CONTENT_TYPE)
+                        .and_then(|value| value.to_str().ok())
+                        .unwrap_or_default()
+                        .to_string()
+                });
+                let error = if !status.is_success() {
+                    Some(status.to_string())
+                } else {
+                    content_type
+                        .as_ref()
+                        .filter(|content_type| {
+                            !content_type.trim().to_lowercase().starts_with("image/")
+                        })
+                        .map(|content_type| {
+                            format!("Not an image (Content-Type: {})", content_type)
+                        })
+                };
+                return LinkCheck {
+                    id,
+                    url: url.to_string(),
+                    status: Some(status.as_u16()),
+                    attempts,
+                    alive: error.is_none(),
+                    error,
+                    content_type,
+                };
+            }
+            Err(e) if (e.is_timeout() || e.is_connect()) && attempts < max_attempts => {
+                tokio::time::sleep(retry_delay).await;
+            }
+            Err(e) => {
+                return LinkCheck {
+                    id,
+                    url: url.to_string(),
+                    status: e.status().map(|status| status.as_u16()),
+                    attempts,
+                    alive: false,
+                    error: Some(e.to_string()),
+                    content_type: None,
+                };
+            }
+        }
+    }
+}
+
+/// Returns the key of the host of a URL used to bound the concurrency, i.e. its last two labels
+/// (e.g. `example.com` for `blog.example.com`), so that the subdomains of a host share the same limit.
+/// IP addresses are used as they are.
+fn host_key(url: &reqwest::Url) -> String {
+    let host = url.host_str().unwrap_or_default().trim_end_matches('.');
+    // IPv6 addresses are enclosed in brackets in URLs.
+    if host.starts_with('[') || host.parse::<std::net::IpAddr>().is_ok() {
+        return host.to_string();
+    }
+    let labels: Vec<&str> = host.rsplitn(3, '.').take(2).collect();
+    labels.into_iter().rev().collect::<Vec<_>>().join(".")
+}
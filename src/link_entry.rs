@@ -1,15 +1,45 @@
+use crate::github_api_responses::{Label, Milestone};
+
 /// The structure of an individual link entry, retrieved from the GitHub issue,
 /// to be added to the generated data file.
 #[derive(Clone)]
-pub(crate) struct LinkEntry {
-    /// The unique identifier for the link entry, same as the ID of the GitHub issue.
-    pub(crate) id: usize,
+pub struct LinkEntry {
+    /// The unique identifier for the link entry, same as the ID of the GitHub issue,
+    /// or the position (starting from 1) of a static entry in the static entries file.
+    pub id: usize,
+    /// The number of the GitHub issue, as shown in the repository, if the entry comes from an issue.
+    pub number: Option<usize>,
+    /// The URL of the GitHub issue on GitHub, if known.
+    pub html_url: Option<String>,
     /// The list of GitHub labels associated with the issue.
-    pub(crate) labels: Vec<String>,
+    pub labels: Vec<Label>,
+    /// The milestone of the GitHub issue, if any.
+    pub milestone: Option<Milestone>,
     /// The JSON data of the link entry contained in the issue body.
-    pub(crate) json_data: serde_json::Value,
+    pub json_data: serde_json::Value,
     /// The time when the issue for submitting the link entry was created.
-    pub(crate) created_at: chrono::DateTime<chrono::Utc>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
     /// The time when the issue for submitting the link entry was last updated.
-    pub(crate) updated_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+    /// The unique slug of the link entry, if slugs are enabled in the output configuration.
+    pub slug: Option<String>,
+    /// Whether the issue of the link entry carries the configured draft label.
+    pub draft: bool,
+}
+
+impl LinkEntry {
+    /// Returns whether the issue of the link entry carries the label with the given name.
+    pub fn has_label(&self, name: &str) -> bool {
+        self.labels.iter().any(|label| label.name == name)
+    }
+
+    /// Returns the GitHub label with the given name, if the issue of the link entry carries it.
+    pub fn label(&self, name: &str) -> Option<&Label> {
+        self.labels.iter().find(|label| label.name == name)
+    }
+
+    /// Returns the ID of the GitHub issue of the link entry, or `None` for a static entry.
+    pub fn issue_id(&self) -> Option<usize> {
+        self.number.map(|_| self.id)
+    }
 }
@@ -0,0 +1,133 @@
+// This file contains the logging of the script, as human-readable text or as JSON lines.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Whether the logs are written as JSON lines instead of human-readable text.
+static JSON_LOGS: AtomicBool = AtomicBool::new(false);
+
+/// The format of the logs.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum LogFormat {
+    /// Human-readable text, the messages as they are.
+    #[default]
+    Text,
+    /// One JSON object per line, with the time, the level, the message, and the fields of the event, if any.
+    Json,
+}
+
+/// The level of a log message.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Level {
+    Info,
+    Warn,
+    Error,
+}
+
+impl Level {
+    /// Returns the name of the level in the JSON logs.
+    fn as_str(self) -> &'static str {
+        match self {
+            Level::Info => "info",
+            Level::Warn => "warn",
+            Level::Error => "error",
+        }
+    }
+}
+
+/// Sets the format of all the logs written afterwards, the logs being written as text by default.
+pub fn set_format(format: LogFormat) {
+    JSON_LOGS.store(format == LogFormat::Json, Ordering::Relaxed);
+}
+
+/// Returns whether the logs are written as JSON lines.
+pub fn is_json() -> bool {
+    JSON_LOGS.load(Ordering::Relaxed)
+}
+
+/// This function writes a log message to the standard output.
+///
+/// In the JSON format, the message is trimmed, and an empty message (e.g. a blank line separating
+/// the sections of the text logs) is not written.
+///
+/// ## Arguments
+/// - `level`: The level of the message.
+/// - `message`: The message.
+pub fn log(level: Level, message: &str) {
+    event(level, None, message, serde_json::Map::new());
+}
+
+/// This function writes a significant event of the run (e.g. a rejected issue) to the standard output.
+/// In the text format, only the message is written; in the JSON format, the `event` name and the `fields`
+/// are added to the JSON object, so that the log aggregators can filter and aggregate the events.
+///
+/// ## Arguments
+/// - `level`: The level of the event.
+/// - `event`: The stable name of the event (e.g. `issue_rejected`), if any.
+/// - `message`: The human-readable message.
+/// - `fields`: The fields describing the event (e.g. the ID of the issue).
+pub fn event(
+    level: Level,
+    event: Option<&str>,
+    message: &str,
+    fields: serde_json::Map<String, serde_json::Value>,
+) {
+    if !JSON_LOGS.load(Ordering::Relaxed) {
+        println!("{}", message);
+        return;
+    }
+    let message = message.trim();
+    if message.is_empty() && event.is_none() {
+        return;
+    }
+
+    let mut object = serde_json::Map::new();
+    object.insert(
+        "timestamp".to_string(),
+        serde_json::Value::String(chrono::Utc::now().to_rfc3339()),
+    );
+    object.insert(
+        "level".to_string(),
+        serde_json::Value::String(level.as_str().to_string()),
+    );
+    if let Some(event) = event {
+        object.insert(
+            "event".to_string(),
+            serde_json::Value::String(event.to_string()),
+        );
+    }
+    object.insert(
+        "message".to_string(),
+        serde_json::Value::String(message.to_string()),
+    );
+    for (key, value) in fields {
+        object.entry(key).or_insert(value);
+    }
+    println!("{}", serde_json::Value::Object(object));
+}
+
+/// Writes an informational log message, formatted like `println!`.
+#[macro_export]
+macro_rules! log_info {
+    ($($arg:tt)*) => {
+        $crate::logging::log($crate::logging::Level::Info, &format!($($arg)*))
+    };
+}
+
+/// Writes a warning log message, formatted like `println!`.
+#[macro_export]
+macro_rules! log_warn {
+    ($($arg:tt)*) => {
+        $crate::logging::log($crate::logging::Level::Warn, &format!($($arg)*))
+    };
+}
+
+/// Writes an error log message, formatted like `println!`.
+#[macro_export]
+macro_rules! log_error {
+    ($($arg:tt)*) => {
+        $crate::logging::log($crate::logging::Level::Error, &format!($($arg)*))
+    };
+}
+
+// The macros are exported at the root of the crate, and re-exported here next to the functions they call.
+pub use crate::{log_error, log_info, log_warn};
@@ -0,0 +1,114 @@
+// This file contains the metrics of a run, in the Prometheus text exposition format.
+
+use crate::summary::Summary;
+use std::fmt::Write;
+
+/// This function renders the counts of the summary of a run as Prometheus gauges,
+/// so that a scraper can alert on a sudden drop of the number of entries.
+///
+/// See: https://prometheus.io/docs/instrumenting/exposition_formats/#text-based-format
+///
+/// ## Arguments
+/// - `summary`: The summary of the run.
+/// - `timestamp`: The time of the run, as a Unix timestamp in seconds.
+///
+/// ## Returns
+/// The content of the metrics file, ending with a line break.
+pub(crate) fn render_metrics(summary: &Summary, timestamp: i64) -> String {
+    let mut metrics = String::new();
+
+    write_metric(
+        &mut metrics,
+        "bfldg_issues_fetched",
+        "The number of issues fetched from GitHub.",
+        &[(None, summary.issues_fetched as f64)],
+    );
+    write_metric(
+        &mut metrics,
+        "bfldg_valid_entries",
+        "The number of issues containing valid entry data.",
+        &[(None, summary.valid_entries as f64)],
+    );
+    write_metric(
+        &mut metrics,
+        "bfldg_active_entries",
+        "The number of valid entries that are active and not excluded or dropped as duplicates.",
+        &[(None, summary.active_entries as f64)],
+    );
+    let groups: Vec<(Option<(&str, &str)>, f64)> = summary
+        .groups
+        .iter()
+        .map(|group| (Some(("group", group.group.as_str())), group.entries as f64))
+        .collect();
+    write_metric(
+        &mut metrics,
+        "bfldg_entries_total",
+        "The number of entries in each top-level group.",
+        &groups,
+    );
+    let reasons: Vec<(Option<(&str, &str)>, f64)> = summary
+        .rejected_by_reason
+        .iter()
+        .map(|(reason, count)| (Some(("reason", reason.as_str())), *count as f64))
+        .collect();
+    write_metric(
+        &mut metrics,
+        "bfldg_rejected_total",
+        "The number of rejected issues for each rejection reason.",
+        &reasons,
+    );
+    write_metric(
+        &mut metrics,
+        "bfldg_dead_links",
+        "The number of active entries whose link is not reachable.",
+        &[(None, summary.dead_links as f64)],
+    );
+    write_metric(
+        &mut metrics,
+        "bfldg_last_run_duration_seconds",
+        "The time the last run took, in seconds.",
+        &[(None, summary.duration_ms as f64 / 1000.0)],
+    );
+    write_metric(
+        &mut metrics,
+        "bfldg_last_run_timestamp_seconds",
+        "The time of the last run, as a Unix timestamp.",
+        &[(None, timestamp as f64)],
+    );
+
+    metrics
+}
+
+/// Writes a gauge with its help text and its samples, each with an optional label.
+/// A gauge without any sample (e.g. no rejected issue) is written with its help text only.
+fn write_metric(
+    metrics: &mut String,
+    name: &str,
+    help: &str,
+    samples: &[(Option<(&str, &str)>, f64)],
+) {
+    writeln!(metrics, "# HELP {} {}", name, help).unwrap();
+    writeln!(metrics, "# TYPE {} gauge", name).unwrap();
+    for (label, value) in samples {
+        match label {
+            Some((label, label_value)) => writeln!(
+                metrics,
+                "{}{{{}=\"{}\"}} {}",
+                name,
+                label,
+                escape_label_value(label_value),
+                value
+            ),
+            None => writeln!(metrics, "{} {}", name, value),
+        }
+        .unwrap();
+    }
+}
+
+/// Escapes the backslashes, double quotes and line breaks of a label value.
+fn escape_label_value(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
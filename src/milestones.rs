@@ -0,0 +1,83 @@
+// This file contains the grouping of the entries by the milestone of their issues,
+// e.g. for a "friends added per release" page.
+
+use crate::group_output::GroupOutput;
+use crate::link_entry::LinkEntry;
+use indexmap::IndexMap;
+
+/// The name of the group of the entries whose issue has no milestone.
+pub(crate) const UNSCHEDULED: &str = "Unscheduled";
+
+/// This function groups the entries by the title of the milestone of their issues.
+///
+/// The milestones are ordered by their due date, the milestones without a due date coming after the other ones
+/// in the order of their numbers, and the entries without a milestone are put in a last `Unscheduled` group.
+/// Within each milestone, the entries are ordered by the creation time of their issues, oldest first.
+///
+/// ## Arguments
+/// - `entries`: The entries to be grouped.
+///
+/// ## Returns
+/// The entries of every milestone, mapped to the title of the milestone.
+pub(crate) fn group_by_milestone(entries: Vec<LinkEntry>) -> IndexMap<String, Vec<LinkEntry>> {
+    let mut entries = entries;
+    entries.sort_by_key(|entry| {
+        let milestone = entry.milestone.as_ref();
+        (
+            milestone.is_none(),
+            milestone
+                .and_then(|milestone| milestone.due_on.clone())
+                .is_none(),
+            milestone.and_then(|milestone| milestone.due_on.clone()),
+            milestone.map(|milestone| milestone.number),
+            entry.created_at,
+        )
+    });
+
+    let mut groups: IndexMap<String, Vec<LinkEntry>> = IndexMap::new();
+    for entry in entries {
+        let title = entry
+            .milestone
+            .as_ref()
+            .map_or(UNSCHEDULED.to_string(), |milestone| milestone.title.clone());
+        groups.entry(title).or_default().push(entry);
+    }
+    groups
+}
+
+/// This function converts the entries grouped by milestone into the groups of the output file,
+/// named after the milestones and described by the descriptions of the milestones.
+///
+/// ## Arguments
+/// - `milestone_to_entry_map`: The entries of every milestone, mapped to the title of the milestone.
+/// - `entry_json`: The function converting an entry into its JSON object.
+///
+/// ## Returns
+/// The groups representing the generated data.
+pub(crate) fn generate_milestone_json(
+    milestone_to_entry_map: &IndexMap<String, Vec<LinkEntry>>,
+    entry_json: impl Fn(&LinkEntry) -> serde_json::Value,
+) -> Vec<GroupOutput> {
+    milestone_to_entry_map
+        .iter()
+        .map(|(title, entries)| {
+            let group_desc = entries
+                .iter()
+                .find_map(|entry| entry.milestone.as_ref()?.description.clone())
+                .unwrap_or_default();
+            let (entry_ids, entries) = entries
+                .iter()
+                .map(|entry| (entry.issue_id(), entry_json(entry)))
+                .unzip();
+            GroupOutput {
+                group: title.clone(),
+                group_name: title.clone(),
+                group_desc,
+                group_color: None,
+                entries,
+                entry_ids,
+                children: None,
+            }
+        })
+        .collect()
+}
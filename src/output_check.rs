@@ -0,0 +1,117 @@
+// This file contains the self-check of the generated data, ensuring that it has the shape expected by the website.
+
+use crate::config::OutputKeys;
+use serde_json::Value;
+
+/// This function checks that the generated data has the shape expected by the website:
+/// every group object has exactly the configured `group`, `group_name`, `group_desc` and `entries` keys
+/// (and `children` for the groups with child groups, and `group_color` for the groups with a color),
+/// and every entry has the required fields
+/// as non-empty strings.
+///
+/// ## Arguments
+/// - `groups`: The generated JSON data of the groups.
+/// - `keys`: The configured key names of the group objects.
+/// - `required_entry_fields`: The fields every entry must have.
+///
+/// ## Returns
+/// The descriptions of all the problems found, empty if the generated data is valid.
+pub(crate) fn check_output(
+    groups: &[Value],
+    keys: &OutputKeys,
+    required_entry_fields: &[String],
+) -> Vec<String> {
+    let mut problems: Vec<String> = Vec::new();
+    for (index, group) in groups.iter().enumerate() {
+        check_group(
+            keys,
+            group,
+            &format!("groups[{}]", index),
+            required_entry_fields,
+            &mut problems,
+        );
+    }
+    problems
+}
+
+/// Checks a group object and its child groups, adding the problems found to `problems`.
+fn check_group(
+    keys: &OutputKeys,
+    group: &Value,
+    path: &str,
+    required_entry_fields: &[String],
+    problems: &mut Vec<String>,
+) {
+    let Some(object) = group.as_object() else {
+        problems.push(format!("{} is not an object.", path));
+        return;
+    };
+
+    let group_keys = [
+        &keys.group,
+        &keys.group_name,
+        &keys.group_desc,
+        &keys.entries,
+    ];
+    for key in group_keys {
+        if !object.contains_key(key) {
+            problems.push(format!("{} is missing the `{}` key.", path, key));
+        }
+    }
+    for key in object.keys() {
+        if !group_keys.contains(&key) && *key != keys.children && *key != keys.group_color {
+            problems.push(format!("{} has the unexpected `{}` key.", path, key));
+        }
+    }
+    for key in [
+        &keys.group,
+        &keys.group_name,
+        &keys.group_desc,
+        &keys.group_color,
+    ] {
+        if object.get(key).is_some_and(|value| !value.is_string()) {
+            problems.push(format!("{}.{} is not a string.", path, key));
+        }
+    }
+
+    match object.get(&keys.entries) {
+        Some(Value::Array(entries)) => {
+            for (index, entry) in entries.iter().enumerate() {
+                let entry_path = format!("{}.{}[{}]", path, keys.entries, index);
+                if !entry.is_object() {
+                    problems.push(format!("{} is not an object.", entry_path));
+                    continue;
+                }
+                for field in required_entry_fields {
+                    let is_set = entry[field]
+                        .as_str()
+                        .is_some_and(|value| !value.trim().is_empty());
+                    if !is_set {
+                        problems.push(format!(
+                            "{} is missing the required `{}` field.",
+                            entry_path, field
+                        ));
+                    }
+                }
+            }
+        }
+        Some(_) => problems.push(format!("{}.{} is not an array.", path, keys.entries)),
+        None => {}
+    }
+
+    match object.get(&keys.children) {
+        Some(Value::Array(children)) => {
+            for (index, child) in children.iter().enumerate() {
+                check_group(
+                    keys,
+                    child,
+                    &format!("{}.{}[{}]", path, keys.children, index),
+                    required_entry_fields,
+                    problems,
+                );
+            }
+        }
+        Some(_) => problems.push(format!("{}.{} is not an array.", path, keys.children)),
+        None => {}
+    }
+}
@@ -0,0 +1,74 @@
+// This file contains the additional friend links pages, generated in the same run from a single fetch of the issues.
+
+use crate::config::Config;
+
+/// The sections of the configuration that a page can override, the other ones (e.g. `github`)
+/// being shared with the base configuration since the issues are only fetched once.
+const PAGE_SECTIONS: [&str; 4] = ["generation", "output", "link_check", "groups"];
+
+/// An additional friend links page, with its own configuration.
+pub(crate) struct Page {
+    /// The name of the page, i.e. the key of its table under `[pages]`.
+    pub(crate) name: String,
+    /// The configuration of the page: the base configuration with the sections overridden by the page.
+    pub(crate) config: Config,
+}
+
+/// This function removes the `[pages]` table from the configuration table, and returns the configuration
+/// table of every page: the base configuration with the sections of the page merged over it.
+/// The tables are merged recursively, and any other value (e.g. the list of groups) is replaced.
+///
+/// ## Arguments
+/// - `config_table`: The parsed base configuration, without the pages afterwards.
+///
+/// ## Returns
+/// The name and the configuration table of every page, in alphabetical order,
+/// or the description of the problem if a page is invalid.
+pub(crate) fn take_page_tables(
+    config_table: &mut toml::Table,
+) -> Result<Vec<(String, toml::Table)>, String> {
+    let pages = match config_table.remove("pages") {
+        Some(toml::Value::Table(pages)) => pages,
+        Some(_) => return Err("`pages` must be a table of pages.".to_string()),
+        None => return Ok(Vec::new()),
+    };
+
+    let mut page_tables: Vec<(String, toml::Table)> = Vec::new();
+    for (name, page) in pages {
+        let toml::Value::Table(page) = page else {
+            return Err(format!("The page \"{}\" must be a table.", name));
+        };
+        if let Some(section) = page
+            .keys()
+            .find(|key| !PAGE_SECTIONS.contains(&key.as_str()))
+        {
+            return Err(format!(
+                "The page \"{}\" cannot override `{}`, only {}.",
+                name,
+                section,
+                PAGE_SECTIONS
+                    .map(|section| format!("`{}`", section))
+                    .join(", ")
+            ));
+        }
+        let mut page_table = config_table.clone();
+        merge_tables(&mut page_table, page);
+        page_tables.push((name, page_table));
+    }
+    Ok(page_tables)
+}
+
+/// Merges the overriding table into the base table: the tables present in both are merged recursively,
+/// and any other value of the overriding table replaces the one of the base table.
+pub(crate) fn merge_tables(base: &mut toml::Table, overrides: toml::Table) {
+    for (key, value) in overrides {
+        match (base.get_mut(&key), value) {
+            (Some(toml::Value::Table(base_table)), toml::Value::Table(override_table)) => {
+                merge_tables(base_table, override_table)
+            }
+            (_, value) => {
+                base.insert(key, value);
+            }
+        }
+    }
+}
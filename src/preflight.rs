@@ -0,0 +1,109 @@
+// This file contains the preflight check of the configuration and of the access to the repository, used by `--check`.
+
+use crate::config::Config;
+use crate::github_api::{self, FetchError};
+use crate::github_api_responses::Repository;
+use crate::github_auth;
+use crate::logging::{log_error, log_info};
+use reqwest::header::{ACCEPT, AUTHORIZATION, USER_AGENT};
+
+/// This function checks that the repository can be read with the configured credentials,
+/// with a single request to the repository, without fetching the issues.
+/// The configuration is already validated when it is loaded.
+///
+/// The OAuth scopes of a classic personal access token are also checked: a private repository
+/// needs the `repo` scope. The fine-grained tokens and the GitHub App tokens have no scopes,
+/// so only their permissions on the repository are checked.
+///
+/// ## Arguments
+/// - `config`: A reference to the `Config` struct that contains the GitHub and network options.
+///
+/// ## Returns
+/// Whether all the checks passed, every problem being logged as an error.
+pub async fn run_preflight(config: &Config) -> bool {
+    let (repository, scopes) = match get_repository(config).await {
+        Ok(response) => response,
+        Err(e) => {
+            log_error!("Failed to Access Repository: {}", e);
+            return false;
+        }
+    };
+    log_info!(
+        "Repository {} is accessible ({}).",
+        repository.full_name,
+        if repository.private {
+            "private"
+        } else {
+            "public"
+        }
+    );
+
+    let mut passed = true;
+    // The permissions are only included in the response for the authenticated requests.
+    if repository
+        .permissions
+        .as_ref()
+        .is_some_and(|permissions| !permissions.pull)
+    {
+        log_error!("The Credentials Cannot Read the Repository.");
+        passed = false;
+    }
+    match scopes {
+        Some(scopes) => {
+            log_info!("Token Scopes: {}", scopes.join(", "));
+            if repository.private && !scopes.iter().any(|scope| scope == "repo") {
+                log_error!(
+                    "The Token Lacks the `repo` Scope Needed to Read the Private Repository."
+                );
+                passed = false;
+            }
+        }
+        None => log_info!("Token Scopes: (none, not a classic personal access token)"),
+    }
+    passed
+}
+
+/// This function retrieves the repository, and the OAuth scopes of the token if it is a classic one.
+///
+/// See: https://docs.github.com/en/rest/repos/repos?apiVersion=2022-11-28#get-a-repository
+///
+/// ## Arguments
+/// - `config`: A reference to a `Config` struct that contains the GitHub API URL, owner, repository name,
+///   and network options.
+///
+/// ## Returns
+/// The repository and the scopes of the token (from the `X-OAuth-Scopes` header), if any,
+/// or a `FetchError` if the repository could not be fetched.
+async fn get_repository(config: &Config) -> Result<(Repository, Option<Vec<String>>), FetchError> {
+    let client = github_api::github_client(&config.network)?;
+    let github_token = github_auth::github_token(config, &client).await?;
+    let url = format!(
+        "{}/repos/{}/{}",
+        config.github.api_url.trim_end_matches('/'),
+        config.github.owner,
+        config.github.repository
+    );
+
+    let request = client
+        .get(&url)
+        .header(USER_AGENT, config.network.user_agent())
+        .header(ACCEPT, "application/vnd.github+json")
+        .header(AUTHORIZATION, format!("Bearer {}", github_token))
+        .header("X-GitHub-Api-Version", "2022-11-28");
+    let res = github_api::send_with_retry(&config.network, request).await?;
+    let scopes = res
+        .headers()
+        .get("X-OAuth-Scopes")
+        .and_then(|value| value.to_str().ok())
+        .map(|value| {
+            value
+                .split(',')
+                .map(|scope| scope.trim().to_string())
+                .filter(|scope| !scope.is_empty())
+                .collect()
+        });
+    let body = res.text().await.map_err(FetchError::Request)?;
+    let repository: Repository = serde_json::from_str(&body).map_err(FetchError::Parse)?;
+
+    Ok((repository, scopes))
+}
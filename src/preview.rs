@@ -0,0 +1,103 @@
+// This file contains the rendering of the HTML preview of the generated data, used for reviewing changes.
+
+use serde_json::Value;
+
+use crate::group_output::GroupOutput;
+use crate::sanitize::escape_html;
+
+/// The style of the preview, inlined so that the document does not need any external asset.
+const STYLE: &str = "\
+body { font-family: sans-serif; margin: 2em; }
+.group { margin-left: 1.5em; }
+.entries { display: flex; flex-wrap: wrap; gap: 1em; padding: 0; list-style: none; }
+.entry { display: flex; gap: 0.75em; align-items: center; width: 20em; padding: 0.75em; border: 1px solid #ccc; border-radius: 0.5em; }
+.entry img { width: 3em; height: 3em; border-radius: 50%; object-fit: cover; }
+.entry p { margin: 0.25em 0 0; color: #666; font-size: 0.9em; }";
+
+/// This function renders a minimal HTML document previewing the generated data,
+/// with every group (and child group) and its entries as a list of cards.
+///
+/// All the values supplied by the contributors are HTML-escaped, and only `http` and `https` URLs
+/// are used for the links and the avatars.
+///
+/// ## Arguments
+/// - `groups`: The generated groups.
+/// - `avatar_field`: The field of the entries containing the avatar URL.
+///
+/// ## Returns
+/// The HTML document, as a string.
+pub(crate) fn render_preview(groups: &[GroupOutput], avatar_field: &str) -> String {
+    let mut html = String::new();
+    html.push_str("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n");
+    html.push_str("<title>Friend Links Preview</title>\n");
+    html.push_str(&format!("<style>\n{}\n</style>\n", STYLE));
+    html.push_str("</head>\n<body>\n<h1>Friend Links Preview</h1>\n");
+    for group in groups {
+        render_group(&mut html, group, avatar_field, 2);
+    }
+    html.push_str("</body>\n</html>\n");
+    html
+}
+
+/// Renders a group, its entries and its child groups, with a heading of the given level.
+fn render_group(html: &mut String, group: &GroupOutput, avatar_field: &str, level: usize) {
+    let level = level.min(6);
+    html.push_str("<section class=\"group\">\n");
+    html.push_str(&format!(
+        "<h{level}>{}</h{level}>\n",
+        escape_html(&group.group_name)
+    ));
+    if !group.group_desc.trim().is_empty() {
+        html.push_str(&format!("<p>{}</p>\n", escape_html(&group.group_desc)));
+    }
+
+    html.push_str("<ul class=\"entries\">\n");
+    for entry in &group.entries {
+        render_entry(html, entry, avatar_field);
+    }
+    html.push_str("</ul>\n");
+
+    for child in group.children.iter().flatten() {
+        render_group(html, child, avatar_field, level + 1);
+    }
+    html.push_str("</section>\n");
+}
+
+/// Renders an entry as a card, with its name linking to its URL and its avatar.
+fn render_entry(html: &mut String, entry: &Value, avatar_field: &str) {
+    let name = string_field(entry, "name")
+        .or_else(|| string_field(entry, "title"))
+        .or_else(|| string_field(entry, "url"))
+        .unwrap_or_default();
+
+    html.push_str("<li class=\"entry\">\n");
+    if let Some(avatar) = string_field(entry, avatar_field).filter(|avatar| is_web_url(avatar)) {
+        html.push_str(&format!(
+            "<img src=\"{}\" alt=\"\" loading=\"lazy\">\n",
+            escape_html(avatar)
+        ));
+    }
+    html.push_str("<div>\n");
+    match string_field(entry, "url").filter(|url| is_web_url(url)) {
+        Some(url) => html.push_str(&format!(
+            "<a href=\"{}\" rel=\"noopener nofollow\">{}</a>\n",
+            escape_html(url),
+            escape_html(name)
+        )),
+        None => html.push_str(&format!("<span>{}</span>\n", escape_html(name))),
+    }
+    if let Some(description) = string_field(entry, "description") {
+        html.push_str(&format!("<p>{}</p>\n", escape_html(description)));
+    }
+    html.push_str("</div>\n</li>\n");
+}
+
+/// Returns the value of a field of a JSON object if it is a non-empty string.
+fn string_field<'a>(value: &'a Value, key: &str) -> Option<&'a str> {
+    value[key].as_str().filter(|value| !value.trim().is_empty())
+}
+
+/// Returns whether a string is an absolute `http` or `https` URL.
+fn is_web_url(value: &str) -> bool {
+    reqwest::Url::parse(value).is_ok_and(|url| matches!(url.scheme(), "http" | "https"))
+}
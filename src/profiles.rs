@@ -0,0 +1,113 @@
+// This file contains the configuration profiles, e.g. for the development, staging and production environments.
+
+use crate::pages;
+
+/// The environment variable selecting the profile, which is not a configuration field override.
+pub(crate) const PROFILE_VAR: &str = "BFLDG_PROFILE";
+
+/// This function removes the `[profiles]` table from the configuration table, and merges the selected profile,
+/// if any, over the configuration. A profile can override any field of the configuration, the tables being
+/// merged recursively and any other value (e.g. the list of groups) being replaced, like the pages.
+///
+/// ## Arguments
+/// - `config_table`: The parsed configuration, without the profiles afterwards.
+/// - `profile`: The name of the selected profile, if any.
+///
+/// ## Returns
+/// `Ok(())` if the profile was merged or none is selected, or the description of the problem
+/// if the profiles are invalid or the selected profile does not exist.
+pub(crate) fn apply_profile(
+    config_table: &mut toml::Table,
+    profile: Option<&str>,
+) -> Result<(), String> {
+    let profiles = match config_table.remove("profiles") {
+        Some(toml::Value::Table(profiles)) => profiles,
+        Some(_) => return Err("`profiles` must be a table of profiles.".to_string()),
+        None => toml::Table::new(),
+    };
+    let Some(name) = profile else {
+        return Ok(());
+    };
+
+    match profiles.get(name) {
+        Some(toml::Value::Table(overrides)) => {
+            if overrides.contains_key("profiles") {
+                return Err(format!(
+                    "The profile \"{}\" cannot define other profiles.",
+                    name
+                ));
+            }
+            pages::merge_tables(config_table, overrides.clone());
+            Ok(())
+        }
+        Some(_) => Err(format!("The profile \"{}\" must be a table.", name)),
+        None if profiles.is_empty() => Err(format!(
+            "Unknown profile \"{}\", no `[profiles.<name>]` table is configured.",
+            name
+        )),
+        None => Err(format!(
+            "Unknown profile \"{}\", the configured profiles are: {}.",
+            name,
+            profiles
+                .keys()
+                .map(|name| format!("\"{}\"", name))
+                .collect::<Vec<String>>()
+                .join(", ")
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONFIG: &str = "[github]\nowner = \"o\"\nrepository = \"r\"\n\
+        [output]\npretty = true\nminify_js = false\n\
+        [[groups]]\nlabel = \"friends\"\n\
+        [profiles.production]\noutput = { pretty = false }\n\
+        [[profiles.production.groups]]\nlabel = \"blogs\"\n\
+        [profiles.staging]\ngithub = { repository = \"staging\" }";
+
+    #[test]
+    fn merges_the_selected_profile_over_the_configuration() {
+        let mut config: toml::Table = toml::from_str(CONFIG).unwrap();
+        apply_profile(&mut config, Some("production")).unwrap();
+        let expected: toml::Table = toml::from_str(
+            "[github]\nowner = \"o\"\nrepository = \"r\"\n\
+             [output]\npretty = false\nminify_js = false\n\
+             [[groups]]\nlabel = \"blogs\"",
+        )
+        .unwrap();
+        // The tables are merged, the list of groups is replaced.
+        assert_eq!(config, expected);
+
+        // Without a selected profile, the profiles are only removed.
+        let mut config: toml::Table = toml::from_str(CONFIG).unwrap();
+        apply_profile(&mut config, None).unwrap();
+        assert!(!config.contains_key("profiles"));
+        assert_eq!(config["github"]["repository"].as_str(), Some("r"));
+    }
+
+    #[test]
+    fn rejects_the_unknown_and_invalid_profiles() {
+        let mut config: toml::Table = toml::from_str(CONFIG).unwrap();
+        assert_eq!(
+            apply_profile(&mut config, Some("dev")),
+            Err(
+                "Unknown profile \"dev\", the configured profiles are: \"production\", \"staging\"."
+                    .to_string()
+            )
+        );
+        let mut config = toml::Table::new();
+        assert_eq!(
+            apply_profile(&mut config, Some("dev")),
+            Err("Unknown profile \"dev\", no `[profiles.<name>]` table is configured.".to_string())
+        );
+        let mut config: toml::Table =
+            toml::from_str("[profiles.dev.profiles.other]\nx = 1").unwrap();
+        assert_eq!(
+            apply_profile(&mut config, Some("dev")),
+            Err("The profile \"dev\" cannot define other profiles.".to_string())
+        );
+    }
+}
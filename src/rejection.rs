@@ -0,0 +1,12 @@
+use serde::Serialize;
+
+/// The structure of an issue rejected by the script, i.e. whose entry is not included in the generated data.
+#[derive(Serialize, Clone, Debug)]
+pub struct Rejection {
+    /// The ID of the rejected issue.
+    pub id: usize,
+    /// A short and stable code of the reason of the rejection (e.g. `invalid_json`), used for reporting.
+    pub reason: &'static str,
+    /// A human-readable description of the reason of the rejection.
+    pub message: String,
+}
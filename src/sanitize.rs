@@ -0,0 +1,62 @@
+// This file contains the helpers used to sanitize the string fields supplied by the contributors.
+
+use crate::config::SanitizeMode;
+
+/// This function sanitizes a string supplied by a contributor, so that it can be safely embedded in HTML.
+///
+/// The control characters are always removed (line breaks and tabs are replaced by a space),
+/// then the HTML special characters are escaped or the HTML tags are stripped, depending on the mode.
+///
+/// ## Arguments
+/// - `value`: The string to sanitize.
+/// - `mode`: How the HTML content is handled.
+///
+/// ## Returns
+/// The sanitized string.
+pub fn sanitize(value: &str, mode: SanitizeMode) -> String {
+    let without_controls: String = value
+        .chars()
+        .filter_map(|c| match c {
+            '\n' | '\r' | '\t' => Some(' '),
+            c if c.is_control() => None,
+            c => Some(c),
+        })
+        .collect();
+
+    match mode {
+        SanitizeMode::Escape => escape_html(&without_controls),
+        SanitizeMode::Strip => strip_html(&without_controls),
+    }
+}
+
+/// Escapes the characters of a string that are special in HTML text and attribute values.
+pub fn escape_html(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Removes the HTML tags (anything between `<` and `>`) from a string,
+/// as well as any remaining unmatched angle bracket.
+fn strip_html(value: &str) -> String {
+    let mut stripped = String::with_capacity(value.len());
+    let mut in_tag = false;
+    for c in value.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            _ if !in_tag => stripped.push(c),
+            _ => {}
+        }
+    }
+    stripped
+}
@@ -0,0 +1,53 @@
+// This file contains the JSON Schema describing the expected format of the friend link entry data.
+
+use serde_json::{json, Value};
+
+/// The URL of the JSON Schema draft used by the schema.
+const SCHEMA_DRAFT: &str = "https://json-schema.org/draft/2020-12/schema";
+
+/// This function returns the JSON Schema of the data of a friend link entry,
+/// i.e. the JSON object in the code block of an issue body.
+///
+/// The schema describes the known fields of the entry (the ones of the issue template);
+/// additional fields are allowed and are kept in the generated data as they are.
+///
+/// ## Returns
+/// The JSON Schema, as a `serde_json::Value`.
+pub(crate) fn entry_schema() -> Value {
+    json!({
+        "$schema": SCHEMA_DRAFT,
+        "title": "Friend Link Entry",
+        "description": "The data of a friend link entry, written in the JSON code block between the DATA_START and DATA_END comments of the issue body.",
+        "type": "object",
+        "properties": {
+            "name": {
+                "description": "The name of the website.",
+                "type": "string"
+            },
+            "title": {
+                "description": "The title of the website, used instead of `name` by the issue template.",
+                "type": "string"
+            },
+            "url": {
+                "description": "The URL of the website.",
+                "type": "string",
+                "format": "uri"
+            },
+            "avatar": {
+                "description": "The URL of the avatar of the website.",
+                "type": "string",
+                "format": "uri"
+            },
+            "screenshot": {
+                "description": "The URL of a screenshot of the website.",
+                "type": "string",
+                "format": "uri"
+            },
+            "description": {
+                "description": "A short description of the website.",
+                "type": "string"
+            }
+        },
+        "additionalProperties": true
+    })
+}
@@ -0,0 +1,30 @@
+// This file contains the helpers used to generate the slugs of the link entries.
+
+/// Converts a string into a slug, i.e. a lowercase string that only contains
+/// alphanumeric characters separated by single dashes.
+///
+/// For example, `blog.example.com` becomes `blog-example-com`.
+pub(crate) fn slugify(value: &str) -> String {
+    let mut slug = String::new();
+    for c in value.chars() {
+        if c.is_alphanumeric() {
+            slug.extend(c.to_lowercase());
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    slug.trim_end_matches('-').to_string()
+}
+
+/// Returns the string a slug is generated from, for the given value of the slug field.
+///
+/// If the value is a URL, its host is used (without a leading `www.`), otherwise the value itself is used.
+pub(crate) fn slug_source(value: &str) -> String {
+    match reqwest::Url::parse(value) {
+        Ok(url) => match url.host_str() {
+            Some(host) => host.trim_start_matches("www.").to_string(),
+            None => value.to_string(),
+        },
+        Err(_) => value.to_string(),
+    }
+}
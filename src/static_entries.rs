@@ -0,0 +1,176 @@
+// This file contains the static entries, maintained in a local file instead of GitHub issues (e.g. legacy links).
+
+use crate::config::{GenerationConfig, StaticPrecedence};
+use crate::generator;
+use crate::github_api_responses::Label;
+use crate::link_entry::LinkEntry;
+use crate::logging::{log_info, log_warn};
+use crate::transform;
+use std::collections::HashMap;
+
+/// This function reads the static entries from the configured file, a JSON array of entry objects,
+/// each listing the labels of the groups it belongs to in its `static_label_field` field
+/// (a string or a list of strings), which is removed from the entry data.
+///
+/// The static entries skip the checks of the issues (e.g. the generation label or the allowed authors),
+/// but their fields are mapped like the ones of the issues. Every static entry is identified
+/// by its position in the file, starting from 1, and its times are the modification time of the file.
+/// An entry that is not an object or has invalid labels is skipped with a warning,
+/// as well as the whole file if it cannot be read or parsed.
+///
+/// ## Arguments
+/// - `generation`: A reference to the `GenerationConfig` struct that contains the static entries options.
+/// - `path`: The path of the static entries file.
+///
+/// ## Returns
+/// The static entries, in the order of the file.
+pub(crate) fn load_static_entries(generation: &GenerationConfig, path: &str) -> Vec<LinkEntry> {
+    let values = match std::fs::read_to_string(path)
+        .map_err(|e| e.to_string())
+        .and_then(|content| {
+            serde_json::from_str::<Vec<serde_json::Value>>(&content).map_err(|e| e.to_string())
+        }) {
+        Ok(values) => values,
+        Err(e) => {
+            log_warn!(
+                "Warning: Failed to Read Static Entries File {}, Skipping Static Entries: {}",
+                path,
+                e
+            );
+            return Vec::new();
+        }
+    };
+    let modified_at = std::fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .map(chrono::DateTime::<chrono::Utc>::from)
+        .unwrap_or_else(|_| chrono::Utc::now());
+
+    let mut entries: Vec<LinkEntry> = Vec::new();
+    for (index, mut json_data) in values.into_iter().enumerate() {
+        let id = index + 1;
+        let Some(object) = json_data.as_object_mut() else {
+            log_warn!(
+                "Warning: Skipping static entry {}: the entry must be a JSON object.",
+                id
+            );
+            continue;
+        };
+        let labels: Vec<String> = match object.remove(&generation.static_label_field) {
+            None => Vec::new(),
+            Some(serde_json::Value::String(label)) => vec![label],
+            Some(serde_json::Value::Array(labels)) => {
+                match labels
+                    .into_iter()
+                    .map(|label| label.as_str().map(str::to_string))
+                    .collect::<Option<Vec<String>>>()
+                {
+                    Some(labels) => labels,
+                    None => {
+                        log_warn!(
+                            "Warning: Skipping static entry {}: the labels in the {} field must be strings.",
+                            id,
+                            generation.static_label_field
+                        );
+                        continue;
+                    }
+                }
+            }
+            Some(_) => {
+                log_warn!(
+                    "Warning: Skipping static entry {}: the {} field must be a string or a list of strings.",
+                    id,
+                    generation.static_label_field
+                );
+                continue;
+            }
+        };
+
+        // Map the fields like the ones of the issues.
+        transform::transform_fields(generation, id, None, &mut json_data);
+
+        entries.push(LinkEntry {
+            id,
+            number: None,
+            html_url: None,
+            milestone: None,
+            labels: labels
+                .into_iter()
+                .map(|name| Label {
+                    id: 0,
+                    name,
+                    description: None,
+                    color: None,
+                })
+                .collect(),
+            json_data,
+            created_at: modified_at,
+            updated_at: modified_at,
+            slug: None,
+            draft: false,
+        });
+    }
+
+    log_info!("Loaded {} static entries from {}.", entries.len(), path);
+    entries
+}
+
+/// This function adds the static entries to the entries of the issues. When a static entry
+/// and an entry of an issue have the same value for the duplicate key (e.g. the same `url`),
+/// only the one taking precedence according to `static_precedence` is kept.
+///
+/// ## Arguments
+/// - `generation`: A reference to the `GenerationConfig` struct that contains the duplicate key and the precedence.
+/// - `entries`: The entries of the issues, already deduplicated.
+/// - `static_entries`: The static entries.
+///
+/// ## Returns
+/// The entries of the issues followed by the static entries, without the colliding entries not taking precedence.
+pub(crate) fn merge_static_entries(
+    generation: &GenerationConfig,
+    entries: Vec<LinkEntry>,
+    static_entries: Vec<LinkEntry>,
+) -> Vec<LinkEntry> {
+    let static_keys: HashMap<String, usize> = static_entries
+        .iter()
+        .filter_map(|entry| {
+            generator::duplicate_key_value(generation, entry).map(|key| (key, entry.id))
+        })
+        .collect();
+
+    let mut merged: Vec<LinkEntry> = Vec::new();
+    let mut overridden: Vec<String> = Vec::new();
+    for entry in entries {
+        let Some(key) = generator::duplicate_key_value(generation, &entry) else {
+            merged.push(entry);
+            continue;
+        };
+        match static_keys.get(&key) {
+            Some(static_id) => {
+                log_info!(
+                    "Static entry {} and entry ID: {} have the same {} \"{}\", keeping the {} entry.",
+                    static_id,
+                    entry.id,
+                    generation.duplicate_identity_field(),
+                    key,
+                    match generation.static_precedence {
+                        StaticPrecedence::Issue => "issue",
+                        StaticPrecedence::Static => "static",
+                    }
+                );
+                match generation.static_precedence {
+                    StaticPrecedence::Issue => {
+                        merged.push(entry);
+                        overridden.push(key);
+                    }
+                    StaticPrecedence::Static => {}
+                }
+            }
+            None => merged.push(entry),
+        }
+    }
+    merged.extend(static_entries.into_iter().filter(|entry| {
+        generator::duplicate_key_value(generation, entry)
+            .map_or(true, |key| !overridden.contains(&key))
+    }));
+    merged
+}
@@ -0,0 +1,62 @@
+use crate::edit_check::EditedEntry;
+use crate::link_check::LinkCheck;
+use crate::rejection::Rejection;
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// The structure of the machine-readable summary of a run, written to `summary.json`.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct Summary {
+    /// The version of the script.
+    pub(crate) version: String,
+    /// The time when the data was generated, as an RFC 3339 string.
+    pub(crate) generated_at: String,
+    /// The time the run took, in milliseconds.
+    pub(crate) duration_ms: u128,
+    /// The configured labels that identify the active issues.
+    pub(crate) generation_label: Vec<String>,
+    /// The number of issues fetched from GitHub.
+    pub(crate) issues_fetched: usize,
+    /// The number of issues containing valid entry data.
+    pub(crate) valid_entries: usize,
+    /// The number of valid entries that are active and not excluded or dropped as duplicates.
+    pub(crate) active_entries: usize,
+    /// The number of entries in each top-level group, in the configured order.
+    pub(crate) groups: Vec<GroupSummary>,
+    /// The number of rejected issues.
+    pub(crate) rejected: usize,
+    /// The number of rejected issues for each rejection reason.
+    pub(crate) rejected_by_reason: BTreeMap<String, usize>,
+    /// The rejected issues, with the reason of their rejection.
+    pub(crate) rejections: Vec<Rejection>,
+    /// The number of active entries whose link is not reachable, if the link checking is enabled.
+    pub(crate) dead_links: usize,
+    /// The results of the checks of the links of the active entries, if the link checking is enabled.
+    pub(crate) link_checks: Vec<LinkCheck>,
+    /// The number of active entries whose avatar is not an image, if the avatar checking is enabled.
+    pub(crate) invalid_avatars: usize,
+    /// The results of the checks of the avatars of the active entries, if the avatar checking is enabled.
+    pub(crate) avatar_checks: Vec<LinkCheck>,
+    /// The active entries whose issue was edited after its approval, if the edit checking is enabled.
+    pub(crate) edited_after_approval: Vec<EditedEntry>,
+}
+
+/// The structure of the summary of a single group.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct GroupSummary {
+    /// The label identifying the group.
+    pub(crate) group: String,
+    /// The number of entries in the group.
+    pub(crate) entries: usize,
+}
+
+/// Counts the rejections for each rejection reason.
+pub(crate) fn count_by_reason(rejections: &[Rejection]) -> BTreeMap<String, usize> {
+    let mut counts: BTreeMap<String, usize> = BTreeMap::new();
+    for rejection in rejections {
+        *counts.entry(rejection.reason.to_string()).or_default() += 1;
+    }
+    counts
+}
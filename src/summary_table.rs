@@ -0,0 +1,75 @@
+// This file contains the compact summary table printed to the terminal at the end of a run.
+
+use std::io::IsTerminal;
+
+/// The ANSI escape code resetting the style.
+const RESET: &str = "\x1b[0m";
+/// The ANSI escape code of bold text.
+const BOLD: &str = "\x1b[1m";
+/// The ANSI escape code of red text.
+const RED: &str = "\x1b[31m";
+/// The ANSI escape code of green text.
+const GREEN: &str = "\x1b[32m";
+
+/// This function returns whether the summary table can be colorized, i.e. the standard output
+/// is a terminal and the `NO_COLOR` environment variable is not set (see https://no-color.org),
+/// so that the CI logs stay plain.
+pub(crate) fn use_color() -> bool {
+    std::io::stdout().is_terminal()
+        && std::env::var_os("NO_COLOR").map_or(true, |value| value.is_empty())
+}
+
+/// This function renders the summary table of a run: the number of entries of each group,
+/// followed by the number of rejected issues, highlighted in red if there are any.
+///
+/// ## Arguments
+/// - `groups`: The name and number of entries of each top-level group, in the configured order.
+/// - `rejected`: The number of rejected issues.
+/// - `color`: Whether to colorize the table with ANSI escape codes.
+///
+/// ## Returns
+/// The rendered table, ending with a line break.
+pub(crate) fn render_summary_table(
+    groups: &[(String, usize)],
+    rejected: usize,
+    color: bool,
+) -> String {
+    let style = |code: &'static str| if color { code } else { "" };
+
+    // Size the columns to fit the longest group name and the largest count.
+    let name_width = groups
+        .iter()
+        .map(|(name, _)| name.chars().count())
+        .chain(["Group".len(), "Rejected".len()])
+        .max()
+        .unwrap_or_default();
+    let count_width = groups
+        .iter()
+        .map(|(_, count)| count.to_string().len())
+        .chain([rejected.to_string().len(), "Entries".len()])
+        .max()
+        .unwrap_or_default();
+    let separator = format!("{}\n", "-".repeat(name_width + count_width + 2));
+
+    let mut table = format!(
+        "{}{:<name_width$}  {:>count_width$}{}\n",
+        style(BOLD),
+        "Group",
+        "Entries",
+        style(RESET)
+    );
+    table.push_str(&separator);
+    for (name, count) in groups {
+        table.push_str(&format!("{:<name_width$}  {:>count_width$}\n", name, count));
+    }
+    table.push_str(&separator);
+    let rejected_style = if rejected > 0 { RED } else { GREEN };
+    table.push_str(&format!(
+        "{}{:<name_width$}  {:>count_width$}{}\n",
+        style(rejected_style),
+        "Rejected",
+        rejected,
+        style(RESET)
+    ));
+    table
+}
@@ -0,0 +1,155 @@
+// This file contains the stub HTTP server used by the tests in place of the GitHub API,
+// and the fixtures of the responses it serves.
+
+use std::sync::{Arc, Mutex};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+
+/// A canned response of the stub server.
+#[derive(Clone)]
+pub(crate) struct StubResponse {
+    /// The status code of the response.
+    pub(crate) status: u16,
+    /// The additional headers of the response.
+    pub(crate) headers: Vec<(&'static str, String)>,
+    /// The body of the response.
+    pub(crate) body: String,
+}
+
+impl StubResponse {
+    /// Returns a successful response with a JSON body.
+    pub(crate) fn ok(body: impl Into<String>) -> StubResponse {
+        StubResponse::status(200, body)
+    }
+
+    /// Returns a response with the given status code and body.
+    pub(crate) fn status(status: u16, body: impl Into<String>) -> StubResponse {
+        StubResponse {
+            status,
+            headers: Vec::new(),
+            body: body.into(),
+        }
+    }
+
+    /// Adds a header to the response.
+    pub(crate) fn header(mut self, name: &'static str, value: impl Into<String>) -> StubResponse {
+        self.headers.push((name, value.into()));
+        self
+    }
+}
+
+/// A stub HTTP server listening on a local port, answering the requests with canned responses.
+pub(crate) struct StubServer {
+    /// The base URL of the server, e.g. `http://127.0.0.1:12345`.
+    pub(crate) url: String,
+    /// The request targets (the path and the query) received by the server, in order.
+    requests: Arc<Mutex<Vec<String>>>,
+}
+
+impl StubServer {
+    /// Starts a server answering the requests with the given responses, in order, whatever their path.
+    /// The last response is repeated once all the other ones were served.
+    pub(crate) async fn start(responses: Vec<StubResponse>) -> StubServer {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let requests: Arc<Mutex<Vec<String>>> = Arc::default();
+
+        let received = Arc::clone(&requests);
+        tokio::spawn(async move {
+            let mut served = 0;
+            loop {
+                let Ok((mut stream, _)) = listener.accept().await else {
+                    return;
+                };
+                // Read the request head, the requests of the client never have a body.
+                let mut head: Vec<u8> = Vec::new();
+                let mut buffer = [0u8; 1024];
+                while !head.windows(4).any(|window| window == b"\r\n\r\n") {
+                    match stream.read(&mut buffer).await {
+                        Ok(0) | Err(_) => break,
+                        Ok(read) => head.extend_from_slice(&buffer[..read]),
+                    }
+                }
+                let head = String::from_utf8_lossy(&head);
+                let target = head.split_whitespace().nth(1).unwrap_or_default();
+                received.lock().unwrap().push(target.to_string());
+
+                let response = &responses[served.min(responses.len() - 1)];
+                served += 1;
+                let mut raw = format!(
+                    "HTTP/1.1 {} Stub\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n",
+                    response.status,
+                    response.body.len()
+                );
+                for (name, value) in &response.headers {
+                    raw.push_str(&format!("{}: {}\r\n", name, value));
+                }
+                raw.push_str("\r\n");
+                raw.push_str(&response.body);
+                let _ = stream.write_all(raw.as_bytes()).await;
+                let _ = stream.shutdown().await;
+            }
+        });
+
+        StubServer { url, requests }
+    }
+
+    /// Returns the request targets received so far, in order.
+    pub(crate) fn requests(&self) -> Vec<String> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+/// Returns the JSON object of an open issue, as returned by the issues endpoint, with the given labels and body.
+pub(crate) fn issue_json(id: usize, labels: &[&str], body: Option<&str>) -> serde_json::Value {
+    serde_json::json!({
+        "id": id,
+        "url": format!("https://api.github.com/repos/o/r/issues/{}", id),
+        "html_url": format!("https://github.com/o/r/issues/{}", id),
+        "number": id,
+        "state": "open",
+        "title": format!("Issue {}", id),
+        "body": body,
+        "labels": labels
+            .iter()
+            .enumerate()
+            .map(|(index, name)| serde_json::json!({ "id": index, "name": name, "description": null }))
+            .collect::<Vec<_>>(),
+        "closed_at": null,
+        "created_at": format!("2024-01-01T00:00:{:02}Z", id % 60),
+        "updated_at": format!("2024-01-01T00:00:{:02}Z", id % 60),
+    })
+}
+
+/// Returns the body of an issue submitting an entry with the given name and URL.
+pub(crate) fn entry_body(name: &str, url: &str) -> String {
+    format!(
+        "<!-- DATA_START -->\n```json\n{}\n```\n<!-- DATA_END -->",
+        serde_json::json!({ "name": name, "url": url })
+    )
+}
+
+/// Returns the body of a page of issues with the IDs in the given range, all submitting a valid entry
+/// and carrying the given labels.
+pub(crate) fn issues_page(ids: std::ops::Range<usize>, labels: &[&str]) -> String {
+    let issues: Vec<serde_json::Value> = ids
+        .map(|id| {
+            let body = entry_body(
+                &format!("Site {}", id),
+                &format!("https://{}.example.com", id),
+            );
+            issue_json(id, labels, Some(&body))
+        })
+        .collect();
+    serde_json::Value::Array(issues).to_string()
+}
+
+/// The body of a 403 response of GitHub when the credentials cannot access the repository.
+pub(crate) const FORBIDDEN_BODY: &str = r#"{"message":"Resource not accessible by personal access token","documentation_url":"https://docs.github.com/rest"}"#;
+
+/// The body of a 403 response of GitHub when the primary rate limit is exceeded.
+pub(crate) const RATE_LIMITED_BODY: &str =
+    r#"{"message":"API rate limit exceeded for user ID 1."}"#;
+
+/// A malformed body, e.g. a response truncated by a proxy.
+pub(crate) const MALFORMED_BODY: &str = r#"[{"id": 1, "url": "#;
@@ -0,0 +1,79 @@
+// This file contains the mapping of the fields submitted by the contributors to the fields of the generated data.
+
+use crate::config::GenerationConfig;
+use crate::logging::log_info;
+use std::collections::HashSet;
+
+/// This function maps the fields of the JSON data of an entry, as configured:
+///
+/// 1. The fields of `rename_fields` are renamed (keeping their position), unless a field with the target name
+///    was also submitted, in which case the submitted target field is kept and the source field is dropped.
+/// 2. If `keep_fields` is not empty, the other fields are dropped.
+/// 3. If `title_field` is set and missing (or `null`), it is set to the title of the issue.
+/// 4. The missing (or `null`) fields of `field_defaults` are set to their default value.
+///
+/// ## Arguments
+/// - `generation`: A reference to the `GenerationConfig` struct that contains the field mapping.
+/// - `entry_id`: The ID of the entry, used for logging.
+/// - `title`: The title of the issue of the entry, if it comes from an issue.
+/// - `json_data`: The JSON data of the entry.
+pub(crate) fn transform_fields(
+    generation: &GenerationConfig,
+    entry_id: usize,
+    title: Option<&str>,
+    json_data: &mut serde_json::Value,
+) {
+    let Some(object) = json_data.as_object_mut() else {
+        return;
+    };
+
+    // Rename the fields, rebuilding the object to keep the order of the fields.
+    if !generation.rename_fields.is_empty() {
+        let submitted: HashSet<String> = object.keys().cloned().collect();
+        let mut renamed = serde_json::Map::new();
+        for (key, value) in std::mem::take(object) {
+            match generation.rename_fields.get(&key) {
+                Some(target) if submitted.contains(target) => log_info!(
+                    "Dropping field {} of entry, as {} is also set, ID: {}",
+                    key,
+                    target,
+                    entry_id
+                ),
+                Some(target) => {
+                    renamed.insert(target.clone(), value);
+                }
+                None => {
+                    renamed.insert(key, value);
+                }
+            }
+        }
+        *object = renamed;
+    }
+
+    // Drop the fields that are not kept.
+    if !generation.keep_fields.is_empty() {
+        object.retain(|key, _| generation.keep_fields.contains(key));
+    }
+
+    // Fall back to the title of the issue for the title field, e.g. for the legacy submissions.
+    if let (Some(field), Some(title)) = (&generation.title_field, title) {
+        if object.get(field).map_or(true, |value| value.is_null()) && !title.trim().is_empty() {
+            log_info!(
+                "Using the issue title for field {} of entry, ID: {}",
+                field,
+                entry_id
+            );
+            object.insert(
+                field.clone(),
+                serde_json::Value::String(title.trim().to_string()),
+            );
+        }
+    }
+
+    // Set the default values of the missing fields.
+    for (key, default) in &generation.field_defaults {
+        if object.get(key).map_or(true, |value| value.is_null()) {
+            object.insert(key.clone(), default.clone());
+        }
+    }
+}
@@ -0,0 +1,45 @@
+// This file contains the webhook notified after a successful run, e.g. to trigger a rebuild of the website.
+
+use crate::config::Config;
+use crate::logging::{log_info, log_warn};
+use reqwest::header::CONTENT_TYPE;
+use std::time::Duration;
+
+/// This function sends the summary of a successful run to the configured webhook, as the JSON body
+/// of a POST request. The run never fails because of the webhook: a warning is printed if the request
+/// fails, times out, or is answered with an error status.
+///
+/// ## Arguments
+/// - `config`: A reference to the `Config` struct that contains the webhook and network options.
+/// - `url`: The URL of the webhook.
+/// - `summary`: The machine-readable summary of the run, as a JSON string.
+pub(crate) async fn notify_webhook(config: &Config, url: &str, summary: &str) {
+    let client = match reqwest::Client::builder()
+        .timeout(Duration::from_secs(config.webhook.timeout_seconds))
+        .user_agent(config.network.user_agent())
+        .build()
+    {
+        Ok(client) => client,
+        Err(e) => {
+            log_warn!("Warning: Failed to Notify Webhook: {}", e);
+            return;
+        }
+    };
+    let result = client
+        .post(url)
+        .header(CONTENT_TYPE, "application/json")
+        .body(summary.to_string())
+        .send()
+        .await;
+
+    match result {
+        Ok(res) if res.status().is_success() => {
+            log_info!("Webhook notified, status: {}.", res.status());
+        }
+        Ok(res) => log_warn!(
+            "Warning: The Webhook Responded with an Error Status: {}",
+            res.status()
+        ),
+        Err(e) => log_warn!("Warning: Failed to Notify Webhook: {}", e),
+    }
+}
@@ -0,0 +1,111 @@
+// This file contains the conversion of the generated data to a YAML document.
+
+use serde_json::Value;
+
+/// This function converts a JSON value to a YAML document, in the block style with a two-space indent.
+///
+/// The strings are always written as double-quoted scalars, with the escapes of JSON (which are valid in YAML)
+/// and the ones of the line separators, so that a value such as `yes`, `1.0` or `null` is never read back
+/// as another type. The keys are only quoted when they are not plain identifiers.
+///
+/// ## Arguments
+/// - `data`: The JSON value.
+///
+/// ## Returns
+/// The YAML document, ending with a line break.
+pub(crate) fn json_to_yaml(data: &Value) -> String {
+    let mut yaml = String::new();
+    match data {
+        Value::Array(items) if !items.is_empty() => write_sequence(items, 0, &mut yaml),
+        Value::Object(map) if !map.is_empty() => write_mapping(map, 0, &mut yaml),
+        value => {
+            yaml.push_str(&scalar(value));
+            yaml.push('\n');
+        }
+    }
+    yaml
+}
+
+/// Writes the items of a non-empty sequence, one `- ` line per item, at the given indentation level.
+fn write_sequence(items: &[Value], level: usize, yaml: &mut String) {
+    let indent = "  ".repeat(level);
+    for item in items {
+        match item {
+            // The first key of a mapping is written on the line of the dash, and the other ones below it.
+            Value::Object(map) if !map.is_empty() => {
+                let mut nested = String::new();
+                write_mapping(map, level + 1, &mut nested);
+                yaml.push_str(&indent);
+                yaml.push_str("- ");
+                yaml.push_str(nested.trim_start());
+            }
+            Value::Array(nested_items) if !nested_items.is_empty() => {
+                yaml.push_str(&indent);
+                yaml.push_str("-\n");
+                write_sequence(nested_items, level + 1, yaml);
+            }
+            value => {
+                yaml.push_str(&format!("{}- {}\n", indent, scalar(value)));
+            }
+        }
+    }
+}
+
+/// Writes the entries of a non-empty mapping, one `key: value` line per entry, at the given indentation level.
+fn write_mapping(map: &serde_json::Map<String, Value>, level: usize, yaml: &mut String) {
+    let indent = "  ".repeat(level);
+    for (key, value) in map {
+        let key = yaml_key(key);
+        match value {
+            Value::Object(nested) if !nested.is_empty() => {
+                yaml.push_str(&format!("{}{}:\n", indent, key));
+                write_mapping(nested, level + 1, yaml);
+            }
+            Value::Array(items) if !items.is_empty() => {
+                yaml.push_str(&format!("{}{}:\n", indent, key));
+                write_sequence(items, level + 1, yaml);
+            }
+            value => {
+                yaml.push_str(&format!("{}{}: {}\n", indent, key, scalar(value)));
+            }
+        }
+    }
+}
+
+/// Returns a key as it is if it is a plain identifier (e.g. `groupName`), or as a double-quoted scalar otherwise.
+fn yaml_key(key: &str) -> String {
+    let is_plain = key
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+    // The keys read as booleans or null by some YAML parsers are quoted too.
+    let is_reserved = matches!(
+        key.to_lowercase().as_str(),
+        "true" | "false" | "null" | "yes" | "no" | "on" | "off" | "y" | "n"
+    );
+    if is_plain && !is_reserved {
+        key.to_string()
+    } else {
+        Value::String(key.to_string()).to_string()
+    }
+}
+
+/// Returns a scalar value (or an empty sequence or mapping) as a YAML flow value.
+fn scalar(value: &Value) -> String {
+    match value {
+        Value::Array(_) => "[]".to_string(),
+        Value::Object(_) => "{}".to_string(),
+        // The line separators, which are not escaped in JSON, are read as line breaks by the YAML 1.1 parsers.
+        Value::String(_) => value
+            .to_string()
+            .replace('\u{85}', "\\N")
+            .replace('\u{2028}', "\\L")
+            .replace('\u{2029}', "\\P")
+            .replace('\u{feff}', "\\uFEFF"),
+        // The JSON representation of the numbers, booleans and null is also valid YAML.
+        value => value.to_string(),
+    }
+}
@@ -0,0 +1,97 @@
+// This file contains the tests of the whole generation, running the script against a stub GitHub API server.
+
+#[allow(dead_code)]
+#[path = "../src/test_server.rs"]
+mod test_server;
+
+use serde_json::json;
+use std::path::{Path, PathBuf};
+use std::process::Output;
+use test_server::{StubResponse, StubServer};
+
+/// Creates an empty working directory for a run of the script.
+fn work_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("bfldg-{}-{}", name, std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+/// Runs the script in the working directory, with a configuration for the repository `o/r`
+/// pointed at the stub server and the given generation options and groups.
+async fn run_script(dir: &Path, server: &StubServer, config: &str) -> Output {
+    let config = format!(
+        "[github]\nowner = \"o\"\nrepository = \"r\"\napi_url = \"{}\"\n\n{}",
+        server.url, config
+    );
+    std::fs::write(dir.join("config.toml"), config).unwrap();
+    tokio::process::Command::new(env!("CARGO_BIN_EXE_blog-friend-links-data-generator"))
+        .current_dir(dir)
+        .env("GITHUB_TOKEN", "test-token")
+        .output()
+        .await
+        .unwrap()
+}
+
+const CONFIG: &str = "[generation]\nlabel = \"active\"\nsort_by_updated_time = false\n\n\
+    [[groups]]\nname = \"Friends\"\ndescription = \"My friends\"\nlabel = \"friends\"";
+
+#[tokio::test]
+async fn generates_the_links_data_from_the_served_issues() {
+    let issues = serde_json::Value::Array(vec![
+        test_server::issue_json(
+            1,
+            &["active", "friends"],
+            Some(&test_server::entry_body("A", "https://a.example.com")),
+        ),
+        test_server::issue_json(2, &["active", "friends"], Some("No data.")),
+        test_server::issue_json(
+            3,
+            &["friends"],
+            Some(&test_server::entry_body(
+                "Inactive",
+                "https://i.example.com",
+            )),
+        ),
+        test_server::issue_json(
+            4,
+            &["active", "friends"],
+            Some(&test_server::entry_body("B", "https://b.example.com")),
+        ),
+    ]);
+    let server = StubServer::start(vec![StubResponse::ok(issues.to_string())]).await;
+    let dir = work_dir("pipeline");
+
+    let output = run_script(&dir, &server, CONFIG).await;
+    assert!(output.status.success(), "{:?}", output);
+    let links_data: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(dir.join("output/linksData.json")).unwrap())
+            .unwrap();
+    assert_eq!(
+        links_data,
+        json!([{
+            "group": "friends",
+            "groupName": "Friends",
+            "groupDesc": "My friends",
+            // The entries are sorted by creation time, newest first.
+            "entries": [
+                { "name": "B", "url": "https://b.example.com" },
+                { "name": "A", "url": "https://a.example.com" },
+            ],
+        }])
+    );
+    assert!(dir.join("output/linksData.mjs").exists());
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[tokio::test]
+async fn fails_on_a_malformed_response() {
+    let server = StubServer::start(vec![StubResponse::ok(test_server::MALFORMED_BODY)]).await;
+    let dir = work_dir("malformed");
+
+    let output = run_script(&dir, &server, CONFIG).await;
+    assert!(!output.status.success());
+    // No output file is written from a partial list of issues.
+    assert!(!dir.join("output").exists());
+    std::fs::remove_dir_all(&dir).unwrap();
+}